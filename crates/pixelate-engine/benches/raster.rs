@@ -0,0 +1,118 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use pixelate::{BlendMode, Bloom, Crt, DrawParams, ImageData, Rect, Renderer, Scanlines};
+use std::hint::black_box;
+
+const WIDTH: u32 = 1920;
+const HEIGHT: u32 = 1080;
+
+// Creates an image with a gradient and a translucent border to exercise blending
+fn test_image(width: u32, height: u32) -> ImageData {
+    let mut rgba_bytes = Vec::with_capacity((width * height * 4) as usize);
+
+    for y in 0..height {
+        for x in 0..width {
+            let border = x < 4 || y < 4 || x >= width - 4 || y >= height - 4;
+            let alpha = if border { 128 } else { 255 };
+            rgba_bytes.extend_from_slice(&[(x % 256) as u8, (y % 256) as u8, 96, alpha]);
+        }
+    }
+
+    ImageData::from_bytes(rgba_bytes, width, height)
+}
+
+fn fills(criterion: &mut Criterion) {
+    let mut renderer = Renderer::offscreen(WIDTH, HEIGHT);
+
+    criterion.bench_function("clear_1080p", |bencher| {
+        bencher.iter(|| renderer.clear(black_box([20, 30, 40, 255])))
+    });
+
+    criterion.bench_function("rectangle_opaque_1080p", |bencher| {
+        bencher.iter(|| renderer.draw_rectangle(0, 0, WIDTH, HEIGHT, black_box([200, 40, 40, 255])))
+    });
+
+    criterion.bench_function("rectangle_translucent_1080p", |bencher| {
+        bencher.iter(|| renderer.draw_rectangle(0, 0, WIDTH, HEIGHT, black_box([200, 40, 40, 128])))
+    });
+
+    criterion.bench_function("circle_filled_radius_400", |bencher| {
+        bencher.iter(|| renderer.fill_circle(960, 540, black_box(400), [40, 200, 40, 255]))
+    });
+}
+
+fn blits(criterion: &mut Criterion) {
+    let mut renderer = Renderer::offscreen(WIDTH, HEIGHT);
+    let sprite = test_image(64, 64);
+    let background = test_image(480, 270);
+
+    criterion.bench_function("image_64_x_200", |bencher| {
+        bencher.iter(|| {
+            for index in 0..200 {
+                let (x, y) = ((index * 97) % WIDTH as i32, (index * 53) % HEIGHT as i32);
+                renderer.draw_image(black_box(&sprite), x, y, 64);
+            }
+        })
+    });
+
+    criterion.bench_function("image_scaled_to_1080p", |bencher| {
+        bencher.iter(|| {
+            renderer.draw_image_region(
+                black_box(&background),
+                Rect::new(0, 0, 480, 270),
+                Rect::new(0, 0, WIDTH, HEIGHT),
+            )
+        })
+    });
+
+    let params = DrawParams::new()
+        .rotation(0.6)
+        .scale(8.0, 8.0)
+        .pivot(32.0, 32.0);
+
+    criterion.bench_function("image_rotated_scaled", |bencher| {
+        bencher.iter(|| renderer.draw_image_ex(black_box(&sprite), 960.0, 540.0, &params))
+    });
+
+    renderer.set_blend_mode(BlendMode::Additive);
+
+    criterion.bench_function("image_additive_64_x_200", |bencher| {
+        bencher.iter(|| {
+            for index in 0..200 {
+                let (x, y) = ((index * 97) % WIDTH as i32, (index * 53) % HEIGHT as i32);
+                renderer.draw_image(black_box(&sprite), x, y, 64);
+            }
+        })
+    });
+}
+
+fn post_processing(criterion: &mut Criterion) {
+    let mut renderer = Renderer::offscreen(WIDTH, HEIGHT);
+    renderer.draw_image_region(
+        &test_image(480, 270),
+        Rect::new(0, 0, 480, 270),
+        Rect::new(0, 0, WIDTH, HEIGHT),
+    );
+
+    renderer.add_post_effect(Scanlines::default());
+
+    criterion.bench_function("post_scanlines_1080p", |bencher| {
+        bencher.iter(|| renderer.apply_post_effects())
+    });
+
+    renderer.clear_post_effects();
+    renderer.add_post_effect(Crt::default());
+
+    criterion.bench_function("post_crt_1080p", |bencher| {
+        bencher.iter(|| renderer.apply_post_effects())
+    });
+
+    renderer.clear_post_effects();
+    renderer.add_post_effect(Bloom::default());
+
+    criterion.bench_function("post_bloom_1080p", |bencher| {
+        bencher.iter(|| renderer.apply_post_effects())
+    });
+}
+
+criterion_group!(benches, fills, blits, post_processing);
+criterion_main!(benches);
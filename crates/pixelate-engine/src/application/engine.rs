@@ -1,11 +1,15 @@
 use crate::{
-    application::Application,
+    application::{Application, HeadlessConfiguration, HeadlessReport, TracedEvent},
     input::{Event, Input, KeyCode, MouseButton},
-    log_error,
-    renderer::Renderer,
-    window::{Window, WindowConfiguration},
+    log_error, log_info, log_warn,
+    renderer::{RecordingFormat, Renderer},
+    window::{RedrawMode, Window, WindowConfiguration},
+};
+use std::{
+    error::Error,
+    path::PathBuf,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
 };
-use std::{error::Error, time::Instant};
 use winit::{
     application::ApplicationHandler,
     event::{ElementState, WindowEvent},
@@ -39,13 +43,190 @@ impl<A: Application> Engine<A> {
     // Runs application with given configuration
     pub fn run(app: A, window_config: WindowConfiguration) -> Result<(), Box<dyn Error>> {
         let event_loop = EventLoop::new()?;
-        event_loop.set_control_flow(ControlFlow::Poll);
+        event_loop.set_control_flow(match window_config.redraw_mode {
+            RedrawMode::Continuous => ControlFlow::Poll,
+            RedrawMode::OnDemand => ControlFlow::Wait,
+        });
 
         let mut engine = Self::new(app, window_config);
         event_loop.run_app(&mut engine)?;
 
         Ok(())
     }
+
+    // Runs application without a display, stepping frames and feeding scripted input
+    pub fn run_headless(
+        app: A,
+        window_config: WindowConfiguration,
+        headless_config: HeadlessConfiguration,
+    ) -> Result<HeadlessReport<A>, Box<dyn Error>> {
+        let window = Window::headless(&window_config);
+        let mut renderer = Renderer::new(&window)?;
+        renderer.set_logical_size(window_config.logical_size);
+        renderer.set_border_color(window_config.border_color);
+
+        let mut engine = Self::new(app, window_config);
+        engine.app.on_init(&window)?;
+        engine.window = Some(window);
+        engine.renderer = Some(renderer);
+
+        let mut steps = headless_config.script.steps().into_iter().peekable();
+        let mut trace = Vec::new();
+
+        for frame in 0..headless_config.frames {
+            while let Some(step) = steps.next_if(|step| step.frame <= frame) {
+                if let Event::WindowResized { width, height } = step.event {
+                    if let Some(window) = engine.window.as_ref() {
+                        window.set_headless_size(width, height);
+                    }
+
+                    if let Some(renderer) = engine.renderer.as_mut() {
+                        renderer.resize(width, height)?;
+                    }
+                }
+
+                trace.push(TracedEvent {
+                    frame,
+                    event: step.event.clone(),
+                });
+
+                engine.dispatch_event(step.event);
+            }
+
+            engine.advance_frame(headless_config.delta_time);
+        }
+
+        let undelivered = steps.count();
+        if undelivered > 0 {
+            log_warn!(
+                "Engine",
+                "{} scripted events were scheduled after the last frame",
+                undelivered
+            );
+        }
+
+        let Engine {
+            mut app,
+            window,
+            renderer,
+            ..
+        } = engine;
+
+        if let Some(window) = window.as_ref() {
+            app.on_shutdown(window);
+        }
+
+        log_info!(
+            "Engine",
+            "Headless run finished after {} frames",
+            headless_config.frames
+        );
+
+        Ok(HeadlessReport {
+            app,
+            renderer: renderer.ok_or("Renderer missing after headless run")?,
+            trace,
+            frames: headless_config.frames,
+        })
+    }
+
+    // Updates input state and forwards a discrete event to application
+    fn dispatch_event(&mut self, event: Event) {
+        let Some(is_headless) = self.window.as_ref().map(Window::is_headless) else {
+            return;
+        };
+
+        // Held keys repeat presses, so only first press triggers a hotkey; scripted runs
+        // never write screenshots or recordings to disk
+        if let Event::KeyPressed(key) = event {
+            if !is_headless && !self.input.is_key_down(key) {
+                self.handle_hotkey(key);
+            }
+        }
+
+        let Some(window) = self.window.as_ref() else {
+            return;
+        };
+
+        self.input.apply_event(&event);
+        self.app.on_event(window, event);
+
+        // Input may change what is shown, so on-demand windows wake up to redraw
+        if self.window_config.redraw_mode == RedrawMode::OnDemand {
+            window.request_redraw();
+        }
+    }
+
+    // Runs engine hotkeys for screenshots and recording
+    fn handle_hotkey(&mut self, key: KeyCode) {
+        if Some(key) == self.window_config.screenshot_key {
+            match self.save_screenshot() {
+                Ok(path) => log_info!("Engine", "Saved screenshot to {}", path.display()),
+                Err(error) => log_error!("Engine", "Failed to save screenshot: {}", error),
+            }
+        }
+
+        if Some(key) == self.window_config.record_key {
+            self.toggle_recording();
+        }
+    }
+
+    // Starts a timestamped recording, or stops running one so it is written in background
+    fn toggle_recording(&mut self) {
+        let Some(renderer) = self.renderer.as_mut() else {
+            return;
+        };
+
+        if renderer.is_recording() {
+            renderer.stop_recording();
+            return;
+        }
+
+        let configuration = self.window_config.recording.clone();
+        let name = match configuration.format {
+            RecordingFormat::Gif => format!("recording-{}.gif", timestamp()),
+            RecordingFormat::PngSequence => format!("recording-{}", timestamp()),
+        };
+
+        if let Err(error) = renderer.start_recording(
+            self.window_config.recording_directory.join(name),
+            configuration,
+        ) {
+            log_error!("Engine", "{}", error);
+        }
+    }
+
+    // Writes last presented frame as a timestamped PNG into screenshot directory
+    fn save_screenshot(&self) -> Result<PathBuf, Box<dyn Error>> {
+        let renderer = self.renderer.as_ref().ok_or("Renderer not created yet")?;
+        let path = self
+            .window_config
+            .screenshot_directory
+            .join(format!("screenshot-{}.png", timestamp()));
+
+        renderer.screenshot().save_png(&path)?;
+
+        Ok(path)
+    }
+
+    // Updates and renders a single frame, then presents it
+    fn advance_frame(&mut self, delta_time: f32) {
+        let (Some(window), Some(renderer)) = (self.window.as_ref(), self.renderer.as_mut()) else {
+            return;
+        };
+
+        self.app.on_update(window, &self.input, delta_time);
+
+        self.app.on_render(window, renderer);
+
+        if let Err(error) = renderer.present_timed(delta_time) {
+            log_error!("Engine", "Failed to present frame: {}", error);
+        }
+
+        if self.window_config.redraw_mode == RedrawMode::Continuous {
+            window.request_redraw();
+        }
+    }
 }
 
 impl<A: Application> ApplicationHandler for Engine<A> {
@@ -53,13 +234,18 @@ impl<A: Application> ApplicationHandler for Engine<A> {
         if self.window.is_none() {
             match Window::new(event_loop, &self.window_config) {
                 Ok(window) => match Renderer::new(&window) {
-                    Ok(renderer) => {
+                    Ok(mut renderer) => {
+                        renderer.set_logical_size(self.window_config.logical_size);
+                        renderer.set_border_color(self.window_config.border_color);
+
                         if let Err(error) = self.app.on_init(&window) {
                             log_error!("Engine", "Failed to initialize application: {}", error);
                             event_loop.exit();
                             return;
                         }
 
+                        window.request_redraw();
+
                         self.renderer = Some(renderer);
                         self.window = Some(window);
                     }
@@ -94,39 +280,30 @@ impl<A: Application> ApplicationHandler for Engine<A> {
             WindowEvent::KeyboardInput { event, .. } => {
                 if let PhysicalKey::Code(key_code) = event.physical_key {
                     let key = KeyCode::from(key_code);
-                    let pressed = event.state == ElementState::Pressed;
-                    self.input.set_key_pressed(key, pressed);
 
                     let input_event = match event.state {
                         ElementState::Pressed => Event::KeyPressed(key),
                         ElementState::Released => Event::KeyReleased(key),
                     };
 
-                    self.app.on_event(window, input_event);
+                    self.dispatch_event(input_event);
                 }
             }
             WindowEvent::MouseInput { state, button, .. } => {
                 let button = MouseButton::from(button);
-                let pressed = state == ElementState::Pressed;
-                self.input.set_mouse_button_pressed(button, pressed);
 
                 let input_event = match state {
                     ElementState::Pressed => Event::MousePressed(button),
                     ElementState::Released => Event::MouseReleased(button),
                 };
 
-                self.app.on_event(window, input_event);
+                self.dispatch_event(input_event);
             }
             WindowEvent::CursorMoved { position, .. } => {
-                self.input
-                    .set_mouse_position(position.x as f32, position.y as f32);
+                let (x, y) = renderer.to_logical(position.x as f32, position.y as f32);
+                let input_event = Event::MouseMoved { x, y };
 
-                let input_event = Event::MouseMoved {
-                    x: position.x as f32,
-                    y: position.y as f32,
-                };
-
-                self.app.on_event(window, input_event);
+                self.dispatch_event(input_event);
             }
             WindowEvent::Resized(size) => {
                 if let Err(error) = renderer.resize(size.width, size.height) {
@@ -138,30 +315,137 @@ impl<A: Application> ApplicationHandler for Engine<A> {
                     height: size.height,
                 };
 
-                self.app.on_event(window, input_event);
+                self.dispatch_event(input_event);
             }
             WindowEvent::RedrawRequested => {
                 let now = Instant::now();
                 let delta_time = now.duration_since(self.last_frame).as_secs_f32();
                 self.last_frame = now;
 
-                self.app.on_update(window, &self.input, delta_time);
-
-                self.app.on_render(window, renderer);
-
-                if let Err(error) = renderer.present() {
-                    log_error!("Engine", "Failed to present frame: {}", error);
-                }
-
-                window.request_redraw();
+                self.advance_frame(delta_time);
             }
             _ => {}
         }
     }
 
     fn about_to_wait(&mut self, _event_loop: &ActiveEventLoop) {
+        if self.window_config.redraw_mode == RedrawMode::OnDemand {
+            return;
+        }
+
         if let Some(window) = &self.window {
             window.request_redraw();
         }
     }
 }
+
+// Formats current time as YYYY-MM-DD_HH-MM-SS-mmm in UTC for file names
+fn timestamp() -> String {
+    let elapsed = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+
+    timestamp_from(elapsed)
+}
+
+// Formats time elapsed since Unix epoch as a UTC timestamp, ignoring time zones and leap seconds
+fn timestamp_from(elapsed: Duration) -> String {
+    let seconds = elapsed.as_secs();
+    let (days, seconds_of_day) = ((seconds / 86_400) as i64, seconds % 86_400);
+
+    // Converts days since 1970-01-01 to a civil date (Howard Hinnant's algorithm)
+    let shifted = days + 719_468;
+    let era = shifted.div_euclid(146_097);
+    let day_of_era = shifted.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + (month <= 2) as i64;
+
+    format!(
+        "{:04}-{:02}-{:02}_{:02}-{:02}-{:02}-{:03}",
+        year,
+        month,
+        day,
+        seconds_of_day / 3_600,
+        seconds_of_day / 60 % 60,
+        seconds_of_day % 60,
+        elapsed.subsec_millis()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Application that changes nothing
+    struct Idle;
+
+    impl Application for Idle {}
+
+    // Creates an engine around a small headless window and renderer
+    fn headless_engine(redraw_mode: RedrawMode) -> Engine<Idle> {
+        let window_config = WindowConfiguration {
+            width: 8,
+            height: 8,
+            redraw_mode,
+            ..Default::default()
+        };
+        let window = Window::headless(&window_config);
+        let renderer = Renderer::new(&window).unwrap();
+
+        let mut engine = Engine::new(Idle, window_config);
+        engine.window = Some(window);
+        engine.renderer = Some(renderer);
+        engine
+    }
+
+    // Checks and clears redraw request of engine window
+    fn redraw_requested(engine: &Engine<Idle>) -> bool {
+        engine.window.as_ref().unwrap().take_redraw_request()
+    }
+
+    #[test]
+    fn timestamp_formats_known_utc_times() {
+        let at = |seconds, millis: u32| timestamp_from(Duration::new(seconds, millis * 1_000_000));
+
+        assert_eq!(at(0, 0), "1970-01-01_00-00-00-000");
+        assert_eq!(at(86_399, 999), "1970-01-01_23-59-59-999");
+        assert_eq!(at(1_709_210_096, 789), "2024-02-29_12-34-56-789");
+        assert_eq!(at(951_782_400, 0), "2000-02-29_00-00-00-000");
+        assert_eq!(at(4_107_542_400 - 1, 0), "2100-02-28_23-59-59-000");
+        assert_eq!(at(4_107_542_400, 0), "2100-03-01_00-00-00-000");
+    }
+
+    #[test]
+    fn on_demand_redraws_only_after_input() {
+        let mut engine = headless_engine(RedrawMode::OnDemand);
+
+        engine.advance_frame(0.1);
+        assert!(!redraw_requested(&engine));
+
+        engine.dispatch_event(Event::MouseMoved { x: 1.0, y: 1.0 });
+        assert!(redraw_requested(&engine));
+
+        engine.advance_frame(0.1);
+        assert!(!redraw_requested(&engine));
+    }
+
+    #[test]
+    fn continuous_redraws_after_every_frame() {
+        let mut engine = headless_engine(RedrawMode::Continuous);
+
+        engine.advance_frame(0.1);
+        assert!(redraw_requested(&engine));
+
+        engine.advance_frame(0.1);
+        assert!(redraw_requested(&engine));
+    }
+}
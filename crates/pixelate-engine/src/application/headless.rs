@@ -0,0 +1,89 @@
+use crate::{
+    input::{Event, KeyCode, MouseButton},
+    renderer::Renderer,
+};
+
+pub struct HeadlessConfiguration {
+    pub frames: u32,
+    pub delta_time: f32,
+    pub script: InputScript,
+}
+
+impl Default for HeadlessConfiguration {
+    // Creates a configuration that runs a single 60 FPS frame with no input
+    fn default() -> Self {
+        Self {
+            frames: 1,
+            delta_time: 1.0 / 60.0,
+            script: InputScript::new(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScriptStep {
+    pub frame: u32,
+    pub event: Event,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct InputScript {
+    steps: Vec<ScriptStep>,
+}
+
+impl InputScript {
+    // Creates an empty input script
+    pub fn new() -> Self {
+        Self { steps: Vec::new() }
+    }
+
+    // Schedules an event to be delivered before given frame updates
+    pub fn event(mut self, frame: u32, event: Event) -> Self {
+        self.steps.push(ScriptStep { frame, event });
+        self
+    }
+
+    // Schedules a key press on given frame and its release on next frame
+    pub fn key_tap(self, frame: u32, key: KeyCode) -> Self {
+        self.event(frame, Event::KeyPressed(key))
+            .event(frame + 1, Event::KeyReleased(key))
+    }
+
+    // Schedules a key held down from first frame until last frame
+    pub fn key_hold(self, first_frame: u32, last_frame: u32, key: KeyCode) -> Self {
+        self.event(first_frame, Event::KeyPressed(key))
+            .event(last_frame, Event::KeyReleased(key))
+    }
+
+    // Schedules a cursor move to given position
+    pub fn mouse_move(self, frame: u32, x: f32, y: f32) -> Self {
+        self.event(frame, Event::MouseMoved { x, y })
+    }
+
+    // Schedules a cursor move and button press, releasing on next frame
+    pub fn click(self, frame: u32, x: f32, y: f32, button: MouseButton) -> Self {
+        self.mouse_move(frame, x, y)
+            .event(frame, Event::MousePressed(button))
+            .event(frame + 1, Event::MouseReleased(button))
+    }
+
+    // Returns scheduled steps ordered by frame, keeping insertion order within a frame
+    pub fn steps(&self) -> Vec<ScriptStep> {
+        let mut steps = self.steps.clone();
+        steps.sort_by_key(|step| step.frame);
+        steps
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct TracedEvent {
+    pub frame: u32,
+    pub event: Event,
+}
+
+pub struct HeadlessReport<A> {
+    pub app: A,
+    pub renderer: Renderer,
+    pub trace: Vec<TracedEvent>,
+    pub frames: u32,
+}
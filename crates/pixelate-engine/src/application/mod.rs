@@ -1,5 +1,7 @@
 mod application;
 mod engine;
+mod headless;
 
 pub use application::Application;
 pub use engine::Engine;
+pub use headless::{HeadlessConfiguration, HeadlessReport, InputScript, ScriptStep, TracedEvent};
@@ -1,9 +1,16 @@
-use crate::{log_info, log_warn, AssetError, ImageData};
+use super::{tiled, BitmapFont, Font, IndexedImage, Palette, SpriteSheet};
+use crate::{log_info, log_warn, tilemap::TileMap, AssetError, ImageData};
 use image::{load_from_memory, GenericImageView};
-use std::{collections::HashMap, fs::read};
+use std::{collections::HashMap, fs::read, path::Path};
 
 pub struct AssetManager {
     image_cache: HashMap<String, ImageData>,
+    sprite_sheet_cache: HashMap<String, SpriteSheet>,
+    bitmap_font_cache: HashMap<String, BitmapFont>,
+    font_cache: HashMap<String, Font>,
+    palette_cache: HashMap<String, Palette>,
+    indexed_image_cache: HashMap<String, IndexedImage>,
+    tile_map_cache: HashMap<String, TileMap>,
 }
 
 impl AssetManager {
@@ -11,6 +18,12 @@ impl AssetManager {
     pub fn new() -> Self {
         Self {
             image_cache: HashMap::new(),
+            sprite_sheet_cache: HashMap::new(),
+            bitmap_font_cache: HashMap::new(),
+            font_cache: HashMap::new(),
+            palette_cache: HashMap::new(),
+            indexed_image_cache: HashMap::new(),
+            tile_map_cache: HashMap::new(),
         }
     }
 
@@ -21,25 +34,218 @@ impl AssetManager {
             return Ok(false);
         }
 
+        let image = Self::decode_image(path)?;
+        let (width, height) = (image.width, image.height);
+
+        self.image_cache.insert(path.to_string(), image);
+
+        log_info!("Assets", "Loaded image '{}' ({}x{})", path, width, height);
+
+        Ok(true)
+    }
+
+    // Loads an image from specified path and slices it into a grid of frames
+    pub fn load_sprite_sheet(
+        &mut self,
+        path: &str,
+        frame_width: u32,
+        frame_height: u32,
+    ) -> Result<bool, AssetError> {
+        if self.sprite_sheet_cache.contains_key(path) {
+            log_warn!("Assets", "Sprite sheet already loaded: {}", path);
+            return Ok(false);
+        }
+
+        let image = Self::decode_image(path)?;
+        let (image_width, image_height) = (image.width, image.height);
+
+        let sprite_sheet = SpriteSheet::new(image, frame_width, frame_height).ok_or_else(|| {
+            AssetError::InvalidFrameSize {
+                path: path.to_string(),
+                frame_width,
+                frame_height,
+                image_width,
+                image_height,
+            }
+        })?;
+
+        log_info!(
+            "Assets",
+            "Loaded sprite sheet '{}' ({} frames of {}x{})",
+            path,
+            sprite_sheet.frame_count(),
+            frame_width,
+            frame_height
+        );
+
+        self.sprite_sheet_cache
+            .insert(path.to_string(), sprite_sheet);
+
+        Ok(true)
+    }
+
+    // Loads a glyph grid image and maps characters to its cells in reading order
+    pub fn load_bitmap_font(
+        &mut self,
+        path: &str,
+        glyph_width: u32,
+        glyph_height: u32,
+        characters: &str,
+    ) -> Result<bool, AssetError> {
+        if self.bitmap_font_cache.contains_key(path) {
+            log_warn!("Assets", "Bitmap font already loaded: {}", path);
+            return Ok(false);
+        }
+
+        let image = Self::decode_image(path)?;
+        let (image_width, image_height) = (image.width, image.height);
+
+        let glyph_count = match (glyph_width, glyph_height) {
+            (0, _) | (_, 0) => 0,
+            _ => ((image_width / glyph_width) * (image_height / glyph_height)) as usize,
+        };
+
+        if glyph_count == 0 {
+            return Err(AssetError::InvalidGlyphSize {
+                path: path.to_string(),
+                glyph_width,
+                glyph_height,
+                image_width,
+                image_height,
+            });
+        }
+
+        let character_count = characters.chars().count();
+
+        let font =
+            BitmapFont::new(image, glyph_width, glyph_height, characters).ok_or_else(|| {
+                AssetError::InvalidCharacterMap {
+                    path: path.to_string(),
+                    character_count,
+                    glyph_count,
+                }
+            })?;
+
+        self.bitmap_font_cache.insert(path.to_string(), font);
+
+        log_info!(
+            "Assets",
+            "Loaded bitmap font '{}' ({} glyphs of {}x{})",
+            path,
+            character_count,
+            glyph_width,
+            glyph_height
+        );
+
+        Ok(true)
+    }
+
+    // Loads a TrueType or OpenType font from specified path
+    pub fn load_font(&mut self, path: &str) -> Result<bool, AssetError> {
+        if self.font_cache.contains_key(path) {
+            log_warn!("Assets", "Font already loaded: {}", path);
+            return Ok(false);
+        }
+
         let bytes = read(path).map_err(|error| AssetError::FileRead {
             path: path.to_string(),
             source: error,
         })?;
 
-        let decoded_image = load_from_memory(&bytes).map_err(|error| AssetError::ImageDecode {
+        let font = Font::from_bytes(&bytes).map_err(|message| AssetError::FontDecode {
+            path: path.to_string(),
+            message: message.to_string(),
+        })?;
+
+        self.font_cache.insert(path.to_string(), font);
+
+        log_info!("Assets", "Loaded font '{}'", path);
+
+        Ok(true)
+    }
+
+    // Loads a JASC, RIFF or raw .pal palette, or a .hex palette with one RRGGBB per line
+    pub fn load_palette(&mut self, path: &str) -> Result<bool, AssetError> {
+        if self.palette_cache.contains_key(path) {
+            log_warn!("Assets", "Palette already loaded: {}", path);
+            return Ok(false);
+        }
+
+        let bytes = read(path).map_err(|error| AssetError::FileRead {
             path: path.to_string(),
             source: error,
         })?;
 
-        let (width, height) = decoded_image.dimensions();
-        let rgba_bytes = decoded_image.into_rgba8().into_raw();
+        let is_hex = Path::new(path)
+            .extension()
+            .is_some_and(|extension| extension.eq_ignore_ascii_case("hex"));
+
+        let palette = match is_hex {
+            true => String::from_utf8(bytes)
+                .map_err(|error| error.to_string())
+                .and_then(|text| Palette::from_hex(&text)),
+            false => Palette::from_pal_bytes(&bytes),
+        }
+        .map_err(|message| AssetError::PaletteDecode {
+            path: path.to_string(),
+            message,
+        })?;
+
+        self.palette_cache.insert(path.to_string(), palette);
 
-        self.image_cache.insert(
-            path.to_string(),
-            ImageData::from_bytes(rgba_bytes, width, height),
+        log_info!("Assets", "Loaded palette '{}'", path);
+
+        Ok(true)
+    }
+
+    // Loads an image and converts its pixels to nearest entries of an already loaded palette
+    pub fn load_indexed_image(
+        &mut self,
+        path: &str,
+        palette_path: &str,
+    ) -> Result<bool, AssetError> {
+        if self.indexed_image_cache.contains_key(path) {
+            log_warn!("Assets", "Indexed image already loaded: {}", path);
+            return Ok(false);
+        }
+
+        let image =
+            IndexedImage::from_image(&Self::decode_image(path)?, self.palette(palette_path)?);
+        let (width, height) = (image.width, image.height);
+
+        self.indexed_image_cache.insert(path.to_string(), image);
+
+        log_info!(
+            "Assets",
+            "Loaded indexed image '{}' ({}x{})",
+            path,
+            width,
+            height
         );
 
-        log_info!("Assets", "Loaded image '{}' ({}x{})", path, width, height);
+        Ok(true)
+    }
+
+    // Loads a Tiled map in .tmx or .tmj form with its tilesets packed into one atlas
+    pub fn load_tile_map(&mut self, path: &str) -> Result<bool, AssetError> {
+        if self.tile_map_cache.contains_key(path) {
+            log_warn!("Assets", "Tile map already loaded: {}", path);
+            return Ok(false);
+        }
+
+        let map = tiled::load_map(path)?;
+
+        log_info!(
+            "Assets",
+            "Loaded tile map '{}' ({}x{} tiles, {} layers, {} objects)",
+            path,
+            map.columns(),
+            map.rows(),
+            map.layer_count(),
+            map.objects().len()
+        );
+
+        self.tile_map_cache.insert(path.to_string(), map);
 
         Ok(true)
     }
@@ -58,6 +264,69 @@ impl AssetManager {
             })
     }
 
+    // Retrieves a reference to a loaded sprite sheet
+    pub fn sprite_sheet(&self, path: &str) -> Result<&SpriteSheet, AssetError> {
+        self.sprite_sheet_cache
+            .get(path)
+            .ok_or_else(|| AssetError::NotLoaded {
+                path: path.to_string(),
+            })
+    }
+
+    // Retrieves a reference to a loaded bitmap font
+    pub fn bitmap_font(&self, path: &str) -> Result<&BitmapFont, AssetError> {
+        self.bitmap_font_cache
+            .get(path)
+            .ok_or_else(|| AssetError::NotLoaded {
+                path: path.to_string(),
+            })
+    }
+
+    // Retrieves a reference to a loaded font
+    pub fn font(&self, path: &str) -> Result<&Font, AssetError> {
+        self.font_cache
+            .get(path)
+            .ok_or_else(|| AssetError::NotLoaded {
+                path: path.to_string(),
+            })
+    }
+
+    // Retrieves a reference to a loaded palette
+    pub fn palette(&self, path: &str) -> Result<&Palette, AssetError> {
+        self.palette_cache
+            .get(path)
+            .ok_or_else(|| AssetError::NotLoaded {
+                path: path.to_string(),
+            })
+    }
+
+    // Retrieves a reference to a loaded indexed image
+    pub fn indexed_image(&self, path: &str) -> Result<&IndexedImage, AssetError> {
+        self.indexed_image_cache
+            .get(path)
+            .ok_or_else(|| AssetError::NotLoaded {
+                path: path.to_string(),
+            })
+    }
+
+    // Retrieves a reference to a loaded tile map
+    pub fn tile_map(&self, path: &str) -> Result<&TileMap, AssetError> {
+        self.tile_map_cache
+            .get(path)
+            .ok_or_else(|| AssetError::NotLoaded {
+                path: path.to_string(),
+            })
+    }
+
+    // Retrieves a loaded tile map for editing tiles or advancing its animations
+    pub fn tile_map_mut(&mut self, path: &str) -> Result<&mut TileMap, AssetError> {
+        self.tile_map_cache
+            .get_mut(path)
+            .ok_or_else(|| AssetError::NotLoaded {
+                path: path.to_string(),
+            })
+    }
+
     // Unloads an image from memory
     pub fn unload_image(&mut self, path: &str) -> bool {
         let removed = self.image_cache.remove(path).is_some();
@@ -67,10 +336,177 @@ impl AssetManager {
         removed
     }
 
-    // Clears all loaded images from memory
+    // Unloads a sprite sheet from memory
+    pub fn unload_sprite_sheet(&mut self, path: &str) -> bool {
+        let removed = self.sprite_sheet_cache.remove(path).is_some();
+        if removed {
+            log_info!("Assets", "Unloaded sprite sheet '{}'", path);
+        }
+        removed
+    }
+
+    // Unloads a bitmap font from memory
+    pub fn unload_bitmap_font(&mut self, path: &str) -> bool {
+        let removed = self.bitmap_font_cache.remove(path).is_some();
+        if removed {
+            log_info!("Assets", "Unloaded bitmap font '{}'", path);
+        }
+        removed
+    }
+
+    // Unloads a font from memory
+    pub fn unload_font(&mut self, path: &str) -> bool {
+        let removed = self.font_cache.remove(path).is_some();
+        if removed {
+            log_info!("Assets", "Unloaded font '{}'", path);
+        }
+        removed
+    }
+
+    // Unloads a palette from memory
+    pub fn unload_palette(&mut self, path: &str) -> bool {
+        let removed = self.palette_cache.remove(path).is_some();
+        if removed {
+            log_info!("Assets", "Unloaded palette '{}'", path);
+        }
+        removed
+    }
+
+    // Unloads an indexed image from memory
+    pub fn unload_indexed_image(&mut self, path: &str) -> bool {
+        let removed = self.indexed_image_cache.remove(path).is_some();
+        if removed {
+            log_info!("Assets", "Unloaded indexed image '{}'", path);
+        }
+        removed
+    }
+
+    // Unloads a tile map from memory
+    pub fn unload_tile_map(&mut self, path: &str) -> bool {
+        let removed = self.tile_map_cache.remove(path).is_some();
+        if removed {
+            log_info!("Assets", "Unloaded tile map '{}'", path);
+        }
+        removed
+    }
+
+    // Clears all loaded assets from memory
     pub fn clear_all(&mut self) {
-        let count = self.image_cache.len();
+        let count = self.image_cache.len()
+            + self.sprite_sheet_cache.len()
+            + self.bitmap_font_cache.len()
+            + self.font_cache.len()
+            + self.palette_cache.len()
+            + self.indexed_image_cache.len()
+            + self.tile_map_cache.len();
         self.image_cache.clear();
-        log_info!("Assets", "Cleared {} images from cache", count);
+        self.sprite_sheet_cache.clear();
+        self.bitmap_font_cache.clear();
+        self.font_cache.clear();
+        self.palette_cache.clear();
+        self.indexed_image_cache.clear();
+        self.tile_map_cache.clear();
+        log_info!("Assets", "Cleared {} assets from cache", count);
+    }
+
+    // Reads and decodes an image file into RGBA pixels
+    pub(super) fn decode_image(path: &str) -> Result<ImageData, AssetError> {
+        let bytes = read(path).map_err(|error| AssetError::FileRead {
+            path: path.to_string(),
+            source: error,
+        })?;
+
+        let decoded_image = load_from_memory(&bytes).map_err(|error| AssetError::ImageDecode {
+            path: path.to_string(),
+            source: error,
+        })?;
+
+        let (width, height) = decoded_image.dimensions();
+        let rgba_bytes = decoded_image.into_rgba8().into_raw();
+
+        Ok(ImageData::from_bytes(rgba_bytes, width, height))
+    }
+}
+
+impl Default for AssetManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::renderer::Renderer;
+    use std::{env, fs};
+
+    const RED: [u8; 4] = [255, 0, 0, 255];
+
+    #[test]
+    fn bitmap_font_loads_glyph_grid_and_breaks_lines() {
+        let directory = env::temp_dir().join(format!("pixelate-font-{}", std::process::id()));
+        fs::create_dir_all(&directory).unwrap();
+
+        // 'A' fills its 2x2 cell, 'B' only lights its top-left pixel
+        let path = directory.join("glyphs.png").display().to_string();
+        let (lit, unlit) = ([255, 255, 255, 255], [0, 0, 0, 0]);
+        let rows = [[lit, lit, lit, unlit], [lit, lit, unlit, unlit]];
+        ImageData::from_bytes(rows.concat().concat(), 4, 2)
+            .save_png(&path)
+            .unwrap();
+
+        let mut assets = AssetManager::new();
+        assert!(assets.load_bitmap_font(&path, 2, 2, "AB").unwrap());
+
+        let font = assets.bitmap_font(&path).unwrap();
+        assert_eq!(font.measure("AB\nA", 1), (4, 4));
+        assert_eq!(font.measure("AB\nA", 2), (8, 8));
+        assert_eq!(font.clone().with_spacing(1, 1).measure("AB\nA", 1), (5, 5));
+
+        let mut renderer = Renderer::offscreen(4, 4);
+        renderer.draw_text(font, "A\nB", 0, 0, RED, 1);
+
+        assert_eq!(renderer.pixel(1, 1), Some(RED));
+        assert_eq!(renderer.pixel(0, 2), Some(RED));
+        assert_eq!(renderer.pixel(1, 3), Some([0, 0, 0, 0]));
+        assert_eq!(renderer.pixel(2, 0), Some([0, 0, 0, 0]));
+
+        let error = AssetManager::new()
+            .load_bitmap_font(&path, 3, 3, "A")
+            .unwrap_err();
+        assert!(matches!(error, AssetError::InvalidGlyphSize { .. }));
+        assert!(error.to_string().contains("glyph size 3x3 for font"));
+
+        let _ = fs::remove_dir_all(&directory);
+    }
+
+    #[test]
+    fn indexed_image_uses_palette_loaded_under_given_path() {
+        let directory = env::temp_dir().join(format!("pixelate-assets-{}", std::process::id()));
+        fs::create_dir_all(&directory).unwrap();
+
+        let palette_path = directory.join("colors.hex").display().to_string();
+        let image_path = directory.join("sprite.png").display().to_string();
+        fs::write(&palette_path, "ff0000\n0000ff\n").unwrap();
+        ImageData::from_bytes(vec![250, 10, 0, 255, 0, 10, 240, 255], 2, 1)
+            .save_png(&image_path)
+            .unwrap();
+
+        let mut assets = AssetManager::new();
+
+        assert!(matches!(
+            assets.load_indexed_image(&image_path, &palette_path),
+            Err(AssetError::NotLoaded { .. })
+        ));
+
+        assets.load_palette(&palette_path).unwrap();
+        assert!(assets
+            .load_indexed_image(&image_path, &palette_path)
+            .unwrap());
+
+        let image = assets.indexed_image(&image_path).unwrap();
+        assert_eq!((image.index(0, 0), image.index(1, 0)), (Some(0), Some(1)));
+
+        let _ = fs::remove_dir_all(&directory);
     }
 }
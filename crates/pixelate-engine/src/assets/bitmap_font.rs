@@ -0,0 +1,113 @@
+use super::{embedded_font, ImageData, SpriteSheet};
+use crate::renderer::Rect;
+use std::{collections::HashMap, sync::OnceLock};
+
+#[derive(Clone)]
+pub struct BitmapFont {
+    glyph_sheet: SpriteSheet,
+    glyph_indices: HashMap<char, usize>,
+    letter_spacing: u32,
+    line_spacing: u32,
+}
+
+impl BitmapFont {
+    // Creates a font from a glyph grid, mapping characters to cells in reading order
+    pub fn new(
+        image: ImageData,
+        glyph_width: u32,
+        glyph_height: u32,
+        characters: &str,
+    ) -> Option<Self> {
+        let glyph_sheet = SpriteSheet::new(image, glyph_width, glyph_height)?;
+
+        if characters.chars().count() > glyph_sheet.frame_count() {
+            return None;
+        }
+
+        let glyph_indices = characters
+            .chars()
+            .enumerate()
+            .map(|(index, character)| (character, index))
+            .collect();
+
+        Some(Self {
+            glyph_sheet,
+            glyph_indices,
+            letter_spacing: 0,
+            line_spacing: 0,
+        })
+    }
+
+    // Returns built-in 5x7 ASCII font that needs no asset files
+    pub fn embedded() -> &'static BitmapFont {
+        static EMBEDDED_FONT: OnceLock<BitmapFont> = OnceLock::new();
+
+        EMBEDDED_FONT.get_or_init(embedded_font::build)
+    }
+
+    // Sets extra pixels between glyphs and between lines, before scaling
+    pub fn with_spacing(mut self, letter_spacing: u32, line_spacing: u32) -> Self {
+        self.letter_spacing = letter_spacing;
+        self.line_spacing = line_spacing;
+        self
+    }
+
+    // Returns width of a single glyph cell in pixels
+    pub fn glyph_width(&self) -> u32 {
+        self.glyph_sheet.frame_width()
+    }
+
+    // Returns height of a single glyph cell in pixels
+    pub fn glyph_height(&self) -> u32 {
+        self.glyph_sheet.frame_height()
+    }
+
+    // Returns horizontal distance between glyph origins at given scale
+    pub fn advance(&self, scale: u32) -> u32 {
+        (self.glyph_width() + self.letter_spacing) * scale
+    }
+
+    // Returns vertical distance between line origins at given scale
+    pub fn line_height(&self, scale: u32) -> u32 {
+        (self.glyph_height() + self.line_spacing) * scale
+    }
+
+    // Checks if font has a glyph for given character
+    pub fn has_glyph(&self, character: char) -> bool {
+        self.glyph_indices.contains_key(&character)
+    }
+
+    // Returns width and height of text block in pixels at given scale
+    pub fn measure(&self, text: &str, scale: u32) -> (u32, u32) {
+        let mut width = 0;
+        let mut line_count = 0;
+
+        for line in text.split('\n') {
+            let character_count = line.chars().count() as u32;
+            let line_width =
+                (character_count * self.advance(scale)).saturating_sub(self.letter_spacing * scale);
+
+            width = width.max(line_width);
+            line_count += 1;
+        }
+
+        let height = line_count * self.line_height(scale) - self.line_spacing * scale;
+
+        (width, height)
+    }
+
+    // Returns glyph sheet image
+    pub(crate) fn image(&self) -> &ImageData {
+        self.glyph_sheet.image()
+    }
+
+    // Returns source rectangle for character, falling back to '?' for unknown glyphs
+    pub(crate) fn glyph(&self, character: char) -> Option<Rect> {
+        let index = self
+            .glyph_indices
+            .get(&character)
+            .or_else(|| self.glyph_indices.get(&'?'))?;
+
+        self.glyph_sheet.frame(*index)
+    }
+}
@@ -14,6 +14,18 @@ pub enum AssetError {
         path: String,
         source: image::ImageError,
     },
+    FontDecode {
+        path: String,
+        message: String,
+    },
+    PaletteDecode {
+        path: String,
+        message: String,
+    },
+    MapDecode {
+        path: String,
+        message: String,
+    },
     NotLoaded {
         path: String,
     },
@@ -22,6 +34,25 @@ pub enum AssetError {
         expected_size: usize,
         actual_size: usize,
     },
+    InvalidFrameSize {
+        path: String,
+        frame_width: u32,
+        frame_height: u32,
+        image_width: u32,
+        image_height: u32,
+    },
+    InvalidGlyphSize {
+        path: String,
+        glyph_width: u32,
+        glyph_height: u32,
+        image_width: u32,
+        image_height: u32,
+    },
+    InvalidCharacterMap {
+        path: String,
+        character_count: usize,
+        glyph_count: usize,
+    },
 }
 
 impl Display for AssetError {
@@ -33,6 +64,19 @@ impl Display for AssetError {
             AssetError::ImageDecode { path, source } => {
                 write!(formatter, "Failed to decode image '{}': {}", path, source)
             }
+            AssetError::FontDecode { path, message } => {
+                write!(formatter, "Failed to decode font '{}': {}", path, message)
+            }
+            AssetError::PaletteDecode { path, message } => {
+                write!(
+                    formatter,
+                    "Failed to decode palette '{}': {}",
+                    path, message
+                )
+            }
+            AssetError::MapDecode { path, message } => {
+                write!(formatter, "Failed to decode map '{}': {}", path, message)
+            }
             AssetError::NotLoaded { path } => {
                 write!(formatter, "Asset '{}' is not loaded", path)
             }
@@ -47,6 +91,43 @@ impl Display for AssetError {
                     path, expected_size, actual_size
                 )
             }
+            AssetError::InvalidFrameSize {
+                path,
+                frame_width,
+                frame_height,
+                image_width,
+                image_height,
+            } => {
+                write!(
+                    formatter,
+                    "Invalid frame size {}x{} for sprite sheet '{}' ({}x{})",
+                    frame_width, frame_height, path, image_width, image_height
+                )
+            }
+            AssetError::InvalidGlyphSize {
+                path,
+                glyph_width,
+                glyph_height,
+                image_width,
+                image_height,
+            } => {
+                write!(
+                    formatter,
+                    "Invalid glyph size {}x{} for font '{}' ({}x{})",
+                    glyph_width, glyph_height, path, image_width, image_height
+                )
+            }
+            AssetError::InvalidCharacterMap {
+                path,
+                character_count,
+                glyph_count,
+            } => {
+                write!(
+                    formatter,
+                    "Character map for font '{}' has {} characters but only {} glyphs",
+                    path, character_count, glyph_count
+                )
+            }
         }
     }
 }
@@ -0,0 +1,250 @@
+use fontdue::{Font as FontdueFont, FontSettings, Metrics};
+use std::{
+    collections::HashMap,
+    mem::take,
+    sync::{Arc, Mutex, MutexGuard},
+};
+
+// Rasterized glyphs kept per font before least recently used ones are dropped
+const DEFAULT_GLYPH_CACHE_CAPACITY: usize = 1024;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TextAlign {
+    #[default]
+    Left,
+    Center,
+    Right,
+}
+
+pub(crate) struct Glyph {
+    pub(crate) metrics: Metrics,
+    pub(crate) coverage: Vec<u8>,
+}
+
+// Glyphs by character and size bits, each stamped with when it was last used
+struct GlyphCache {
+    glyphs: HashMap<(char, u32), (Arc<Glyph>, u64)>,
+    capacity: usize,
+    clock: u64,
+}
+
+impl GlyphCache {
+    // Returns cached glyph, marking it as most recently used
+    fn get(&mut self, key: (char, u32)) -> Option<Arc<Glyph>> {
+        self.clock += 1;
+        let (glyph, last_used) = self.glyphs.get_mut(&key)?;
+        *last_used = self.clock;
+
+        Some(Arc::clone(glyph))
+    }
+
+    // Stores a glyph, dropping least recently used ones beyond capacity
+    fn insert(&mut self, key: (char, u32), glyph: Arc<Glyph>) {
+        self.clock += 1;
+        self.glyphs.insert(key, (glyph, self.clock));
+        self.evict();
+    }
+
+    // Drops least recently used glyphs until cache fits capacity
+    fn evict(&mut self) {
+        while self.glyphs.len() > self.capacity {
+            let oldest = self
+                .glyphs
+                .iter()
+                .min_by_key(|(_, (_, last_used))| *last_used)
+                .map(|(key, _)| *key);
+
+            match oldest {
+                Some(key) => self.glyphs.remove(&key),
+                None => break,
+            };
+        }
+    }
+}
+
+pub struct Font {
+    inner: FontdueFont,
+    glyph_cache: Mutex<GlyphCache>,
+}
+
+impl Font {
+    // Parses a TrueType or OpenType font from raw file bytes
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, &'static str> {
+        let inner = FontdueFont::from_bytes(bytes, FontSettings::default())?;
+
+        Ok(Self {
+            inner,
+            glyph_cache: Mutex::new(GlyphCache {
+                glyphs: HashMap::new(),
+                capacity: DEFAULT_GLYPH_CACHE_CAPACITY,
+                clock: 0,
+            }),
+        })
+    }
+
+    // Returns distance between baselines of consecutive lines at given pixel size
+    pub fn line_height(&self, size: f32) -> f32 {
+        self.inner
+            .horizontal_line_metrics(size)
+            .map_or(size, |metrics| metrics.new_line_size)
+    }
+
+    // Returns distance from top of a line to its baseline at given pixel size
+    pub fn ascent(&self, size: f32) -> f32 {
+        self.inner
+            .horizontal_line_metrics(size)
+            .map_or(size, |metrics| metrics.ascent)
+    }
+
+    // Returns kerning adjustment between two characters at given pixel size
+    pub fn kerning(&self, left: char, right: char, size: f32) -> f32 {
+        self.inner.horizontal_kern(left, right, size).unwrap_or(0.0)
+    }
+
+    // Returns kerned width of a single line of text at given pixel size
+    pub fn measure_line(&self, line: &str, size: f32) -> f32 {
+        let mut width = 0.0;
+        let mut previous = None;
+
+        for character in line.chars() {
+            if let Some(previous) = previous {
+                width += self.kerning(previous, character, size);
+            }
+
+            width += self.inner.metrics(character, size).advance_width;
+            previous = Some(character);
+        }
+
+        width
+    }
+
+    // Returns width and height of text block at given pixel size, breaking lines on '\n'
+    pub fn measure(&self, text: &str, size: f32) -> (f32, f32) {
+        let mut width: f32 = 0.0;
+        let mut line_count = 0;
+
+        for line in text.split('\n') {
+            width = width.max(self.measure_line(line, size));
+            line_count += 1;
+        }
+
+        (width, line_count as f32 * self.line_height(size))
+    }
+
+    // Breaks text into lines no wider than max width, splitting at spaces
+    pub fn wrap(&self, text: &str, size: f32, max_width: f32) -> Vec<String> {
+        let mut lines = Vec::new();
+
+        for paragraph in text.split('\n') {
+            let mut line = String::new();
+
+            for word in paragraph.split_whitespace() {
+                let candidate = if line.is_empty() {
+                    word.to_string()
+                } else {
+                    format!("{} {}", line, word)
+                };
+
+                // A single word wider than max width still gets its own line
+                if !line.is_empty() && self.measure_line(&candidate, size) > max_width {
+                    lines.push(take(&mut line));
+                    line.push_str(word);
+                } else {
+                    line = candidate;
+                }
+            }
+
+            lines.push(line);
+        }
+
+        lines
+    }
+
+    // Returns number of rasterized glyphs held in cache
+    pub fn cached_glyph_count(&self) -> usize {
+        self.cache().glyphs.len()
+    }
+
+    // Returns how many rasterized glyphs cache holds before dropping least recently used ones
+    pub fn glyph_cache_capacity(&self) -> usize {
+        self.cache().capacity
+    }
+
+    // Sets how many rasterized glyphs cache may hold, dropping extra ones right away
+    pub fn set_glyph_cache_capacity(&self, capacity: usize) {
+        let mut cache = self.cache();
+        cache.capacity = capacity;
+        cache.evict();
+    }
+
+    // Drops all rasterized glyphs, e.g. after using many one-off sizes
+    pub fn clear_glyph_cache(&self) {
+        self.cache().glyphs.clear();
+    }
+
+    // Returns rasterized glyph for character at given pixel size, rasterizing on first use
+    pub(crate) fn glyph(&self, character: char, size: f32) -> Arc<Glyph> {
+        let key = (character, size.to_bits());
+
+        if let Some(glyph) = self.cache().get(key) {
+            return glyph;
+        }
+
+        // Rasterizing happens unlocked so other threads can keep reading cached glyphs
+        let (metrics, coverage) = self.inner.rasterize(character, size);
+        let glyph = Arc::new(Glyph { metrics, coverage });
+
+        self.cache().insert(key, Arc::clone(&glyph));
+
+        glyph
+    }
+
+    // Locks glyph cache; a panic while holding it leaves only cached glyphs behind, so it is reused
+    fn cache(&self) -> MutexGuard<'_, GlyphCache> {
+        self.glyph_cache
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assets::AssetManager;
+
+    fn glyph() -> Arc<Glyph> {
+        Arc::new(Glyph {
+            metrics: Metrics::default(),
+            coverage: Vec::new(),
+        })
+    }
+
+    #[test]
+    fn fonts_and_assets_can_move_between_threads() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        fn assert_send<T: Send>() {}
+
+        assert_send_sync::<Font>();
+        assert_send::<AssetManager>();
+    }
+
+    #[test]
+    fn glyph_cache_drops_least_recently_used() {
+        let mut cache = GlyphCache {
+            glyphs: HashMap::new(),
+            capacity: 2,
+            clock: 0,
+        };
+
+        cache.insert(('a', 0), glyph());
+        cache.insert(('b', 0), glyph());
+        assert!(cache.get(('a', 0)).is_some());
+
+        cache.insert(('c', 0), glyph());
+
+        assert_eq!(cache.glyphs.len(), 2);
+        assert!(cache.get(('a', 0)).is_some());
+        assert!(cache.get(('b', 0)).is_none());
+        assert!(cache.get(('c', 0)).is_some());
+    }
+}
@@ -1,3 +1,7 @@
+use image::{save_buffer, ExtendedColorType, ImageError};
+use std::{fs::create_dir_all, path::Path};
+
+#[derive(Clone)]
 pub struct ImageData {
     pub rgba_bytes: Vec<u8>,
     pub width: u32,
@@ -13,4 +17,33 @@ impl ImageData {
             height,
         }
     }
+
+    // Returns RGBA color of pixel at specified coordinates
+    pub fn pixel(&self, x: i32, y: i32) -> Option<[u8; 4]> {
+        if x < 0 || y < 0 || x >= self.width as i32 || y >= self.height as i32 {
+            return None;
+        }
+
+        let index = ((y as u32 * self.width + x as u32) * 4) as usize;
+        let rgba = self.rgba_bytes.get(index..index + 4)?;
+
+        Some([rgba[0], rgba[1], rgba[2], rgba[3]])
+    }
+
+    // Writes image as a PNG file, creating parent directories as needed
+    pub fn save_png(&self, path: impl AsRef<Path>) -> Result<(), ImageError> {
+        let path = path.as_ref();
+
+        if let Some(parent) = path.parent() {
+            create_dir_all(parent).map_err(ImageError::IoError)?;
+        }
+
+        save_buffer(
+            path,
+            &self.rgba_bytes,
+            self.width,
+            self.height,
+            ExtendedColorType::Rgba8,
+        )
+    }
 }
@@ -0,0 +1,98 @@
+use super::{ImageData, Palette};
+use std::collections::HashMap;
+
+#[derive(Clone)]
+pub struct IndexedImage {
+    pub indices: Vec<u8>,
+    pub width: u32,
+    pub height: u32,
+    pub transparent_index: Option<u8>,
+}
+
+impl IndexedImage {
+    // Creates an image of given size with every pixel set to index zero
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            indices: vec![0; (width * height) as usize],
+            width,
+            height,
+            transparent_index: None,
+        }
+    }
+
+    // Converts RGBA pixels to nearest palette entries; pixels under half alpha become transparent
+    pub fn from_image(image: &ImageData, palette: &Palette) -> Self {
+        let has_transparency = image.rgba_bytes.chunks_exact(4).any(|rgba| rgba[3] < 128);
+
+        // Prefer a fully transparent entry, then first unused entry, then give up index 0
+        let transparent_index = has_transparency.then(|| {
+            palette
+                .colors()
+                .iter()
+                .position(|color| color[3] == 0)
+                .or((palette.color_count() < 256).then_some(palette.color_count()))
+                .unwrap_or(0) as u8
+        });
+
+        let mut lookup: HashMap<[u8; 4], u8> = HashMap::new();
+
+        let indices = image
+            .rgba_bytes
+            .chunks_exact(4)
+            .map(|rgba| {
+                if rgba[3] < 128 {
+                    return transparent_index.unwrap_or(0);
+                }
+
+                let color = [rgba[0], rgba[1], rgba[2], 255];
+
+                *lookup
+                    .entry(color)
+                    .or_insert_with(|| palette.nearest_index(color, transparent_index))
+            })
+            .collect();
+
+        Self {
+            indices,
+            width: image.width,
+            height: image.height,
+            transparent_index,
+        }
+    }
+
+    // Returns palette index of pixel at specified coordinates
+    pub fn index(&self, x: i32, y: i32) -> Option<u8> {
+        if x < 0 || y < 0 || x >= self.width as i32 || y >= self.height as i32 {
+            return None;
+        }
+
+        self.indices
+            .get((y as u32 * self.width + x as u32) as usize)
+            .copied()
+    }
+
+    // Returns RGBA color of pixel through a palette, fully transparent for transparent index
+    pub fn pixel(&self, x: i32, y: i32, palette: &Palette) -> Option<[u8; 4]> {
+        let index = self.index(x, y)?;
+
+        if Some(index) == self.transparent_index {
+            return Some([0, 0, 0, 0]);
+        }
+
+        Some(palette.color(index))
+    }
+
+    // Resolves every pixel through a palette into a new RGBA image
+    pub fn to_image(&self, palette: &Palette) -> ImageData {
+        let rgba_bytes = self
+            .indices
+            .iter()
+            .flat_map(|&index| match Some(index) == self.transparent_index {
+                true => [0, 0, 0, 0],
+                false => palette.color(index),
+            })
+            .collect();
+
+        ImageData::from_bytes(rgba_bytes, self.width, self.height)
+    }
+}
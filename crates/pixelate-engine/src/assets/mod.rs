@@ -1,7 +1,19 @@
 mod assets;
+mod bitmap_font;
+mod embedded_font;
 mod error;
+mod font;
 mod image;
+mod indexed_image;
+mod palette;
+mod sprite_sheet;
+mod tiled;
 
 pub use assets::AssetManager;
+pub use bitmap_font::BitmapFont;
 pub use error::AssetError;
+pub use font::{Font, TextAlign};
 pub use image::ImageData;
+pub use indexed_image::IndexedImage;
+pub use palette::Palette;
+pub use sprite_sheet::SpriteSheet;
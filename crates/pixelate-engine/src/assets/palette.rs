@@ -0,0 +1,251 @@
+use std::ops::RangeInclusive;
+
+const PALETTE_SIZE: usize = 256;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Palette {
+    colors: [[u8; 4]; PALETTE_SIZE],
+    color_count: usize,
+}
+
+impl Palette {
+    // Creates a palette with every entry set to opaque black
+    pub fn new() -> Self {
+        Self {
+            colors: [[0, 0, 0, 255]; PALETTE_SIZE],
+            color_count: PALETTE_SIZE,
+        }
+    }
+
+    // Creates a palette from up to 256 colors, filling remaining entries with opaque black
+    pub fn from_colors(colors: &[[u8; 4]]) -> Self {
+        let mut palette = Self::new();
+
+        for (entry, color) in palette.colors.iter_mut().zip(colors) {
+            *entry = *color;
+        }
+
+        palette.color_count = colors.len().min(PALETTE_SIZE);
+
+        palette
+    }
+
+    // Returns RGBA color stored at given index
+    pub fn color(&self, index: u8) -> [u8; 4] {
+        self.colors[index as usize]
+    }
+
+    // Replaces color stored at given index
+    pub fn set_color(&mut self, index: u8, color: [u8; 4]) {
+        self.colors[index as usize] = color;
+    }
+
+    // Returns number of colors palette was defined with; later entries are unused
+    pub fn color_count(&self) -> usize {
+        self.color_count
+    }
+
+    // Returns all 256 entries in index order
+    pub fn colors(&self) -> &[[u8; 4]; PALETTE_SIZE] {
+        &self.colors
+    }
+
+    // Exchanges colors stored at two indices
+    pub fn swap(&mut self, first: u8, second: u8) {
+        self.colors.swap(first as usize, second as usize);
+    }
+
+    // Rotates colors within an inclusive index range by given steps, forward for positive steps
+    pub fn cycle(&mut self, range: RangeInclusive<u8>, steps: i32) {
+        let (start, end) = (*range.start() as usize, *range.end() as usize);
+
+        if start >= end {
+            return;
+        }
+
+        let entries = &mut self.colors[start..=end];
+        let shift = steps.rem_euclid(entries.len() as i32) as usize;
+
+        entries.rotate_right(shift);
+    }
+
+    // Returns index of defined entry closest to an RGB color, skipping an optional excluded index
+    pub fn nearest_index(&self, color: [u8; 4], excluded: Option<u8>) -> u8 {
+        let mut best_index = 0;
+        let mut best_distance = u32::MAX;
+
+        for (index, entry) in self.colors.iter().enumerate().take(self.color_count) {
+            if excluded == Some(index as u8) {
+                continue;
+            }
+
+            let distance = (0..3)
+                .map(|channel| {
+                    let difference = entry[channel] as i32 - color[channel] as i32;
+                    (difference * difference) as u32
+                })
+                .sum::<u32>();
+
+            if distance < best_distance {
+                best_index = index as u8;
+                best_distance = distance;
+
+                if distance == 0 {
+                    break;
+                }
+            }
+        }
+
+        best_index
+    }
+
+    // Parses a JASC text, RIFF or raw 768 byte palette file
+    pub(crate) fn from_pal_bytes(bytes: &[u8]) -> Result<Self, String> {
+        if bytes.starts_with(b"JASC-PAL") {
+            let text = std::str::from_utf8(bytes).map_err(|error| error.to_string())?;
+            return Self::from_jasc(text);
+        }
+
+        if bytes.starts_with(b"RIFF") {
+            return Self::from_riff(bytes);
+        }
+
+        if bytes.len() == PALETTE_SIZE * 3 {
+            let colors: Vec<[u8; 4]> = bytes
+                .chunks_exact(3)
+                .map(|rgb| [rgb[0], rgb[1], rgb[2], 255])
+                .collect();
+            return Ok(Self::from_colors(&colors));
+        }
+
+        Err("unrecognized palette format".to_string())
+    }
+
+    // Parses a text file holding one RRGGBB color per line
+    pub(crate) fn from_hex(text: &str) -> Result<Self, String> {
+        let mut colors = Vec::new();
+
+        for line in text.lines().map(str::trim).filter(|line| !line.is_empty()) {
+            let digits = line.strip_prefix('#').unwrap_or(line);
+
+            let value = match digits.len() {
+                6 => u32::from_str_radix(digits, 16).ok(),
+                _ => None,
+            }
+            .ok_or_else(|| format!("invalid hex color '{}'", line))?;
+
+            colors.push([(value >> 16) as u8, (value >> 8) as u8, value as u8, 255]);
+        }
+
+        Self::checked_from_colors(&colors)
+    }
+
+    // Parses a JASC-PAL text palette with header, version, count and "r g b" lines
+    fn from_jasc(text: &str) -> Result<Self, String> {
+        let mut lines = text.lines().map(str::trim).filter(|line| !line.is_empty());
+
+        if lines.next() != Some("JASC-PAL") {
+            return Err("missing JASC-PAL header".to_string());
+        }
+
+        match lines.next() {
+            Some("0100") => {}
+            Some(version) => return Err(format!("unsupported JASC-PAL version '{}'", version)),
+            None => return Err("missing JASC-PAL version".to_string()),
+        }
+
+        let count: usize = lines
+            .next()
+            .and_then(|line| line.parse().ok())
+            .ok_or("missing color count")?;
+
+        let mut colors = Vec::with_capacity(count);
+
+        for line in lines.take(count) {
+            let channels: Vec<u8> = line
+                .split_whitespace()
+                .map(str::parse)
+                .collect::<Result<_, _>>()
+                .map_err(|_| format!("invalid color '{}'", line))?;
+
+            match channels[..] {
+                [r, g, b] => colors.push([r, g, b, 255]),
+                _ => return Err(format!("invalid color '{}'", line)),
+            }
+        }
+
+        if colors.len() != count {
+            return Err(format!("expected {} colors, found {}", count, colors.len()));
+        }
+
+        Self::checked_from_colors(&colors)
+    }
+
+    // Parses a Microsoft RIFF palette holding a "data" chunk of RGBX entries
+    fn from_riff(bytes: &[u8]) -> Result<Self, String> {
+        let data = bytes
+            .windows(4)
+            .position(|window| window == b"data")
+            .and_then(|offset| bytes.get(offset + 8..))
+            .ok_or("missing data chunk")?;
+
+        let count = match data {
+            [_, _, low, high, ..] => u16::from_le_bytes([*low, *high]) as usize,
+            _ => return Err("truncated data chunk".to_string()),
+        };
+
+        let entries = data
+            .get(4..4 + count * 4)
+            .ok_or("truncated color entries")?;
+
+        let colors: Vec<[u8; 4]> = entries
+            .chunks_exact(4)
+            .map(|rgbx| [rgbx[0], rgbx[1], rgbx[2], 255])
+            .collect();
+
+        Self::checked_from_colors(&colors)
+    }
+
+    fn checked_from_colors(colors: &[[u8; 4]]) -> Result<Self, String> {
+        match colors.len() {
+            0 => Err("palette has no colors".to_string()),
+            count if count > PALETTE_SIZE => {
+                Err(format!("palette has {} colors, maximum is 256", count))
+            }
+            _ => Ok(Self::from_colors(colors)),
+        }
+    }
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn jasc_palette_is_parsed() {
+        let palette =
+            Palette::from_pal_bytes(b"JASC-PAL\r\n0100\r\n2\r\n255 0 0\r\n0 0 255\r\n").unwrap();
+
+        assert_eq!(palette.color_count(), 2);
+        assert_eq!(palette.color(1), [0, 0, 255, 255]);
+    }
+
+    #[test]
+    fn jasc_palette_with_bad_header_is_rejected() {
+        assert_eq!(
+            Palette::from_jasc("JASC-PALETTE\n0100\n1\n0 0 0\n").unwrap_err(),
+            "missing JASC-PAL header"
+        );
+        assert_eq!(
+            Palette::from_jasc("JASC-PAL\n0200\n1\n0 0 0\n").unwrap_err(),
+            "unsupported JASC-PAL version '0200'"
+        );
+        assert!(Palette::from_jasc("JASC-PAL\n1\n0 0 0\n").is_err());
+    }
+}
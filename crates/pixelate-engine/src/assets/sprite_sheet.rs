@@ -0,0 +1,91 @@
+use super::ImageData;
+use crate::renderer::Rect;
+
+#[derive(Clone)]
+pub struct SpriteSheet {
+    image: ImageData,
+    frame_width: u32,
+    frame_height: u32,
+    columns: u32,
+    rows: u32,
+}
+
+impl SpriteSheet {
+    // Slices an image into a grid of equally sized frames, read left-to-right, top-to-bottom
+    pub fn new(image: ImageData, frame_width: u32, frame_height: u32) -> Option<Self> {
+        if frame_width == 0 || frame_height == 0 {
+            return None;
+        }
+
+        let columns = image.width / frame_width;
+        let rows = image.height / frame_height;
+
+        if columns == 0 || rows == 0 {
+            return None;
+        }
+
+        Some(Self {
+            image,
+            frame_width,
+            frame_height,
+            columns,
+            rows,
+        })
+    }
+
+    // Returns underlying image containing all frames
+    pub fn image(&self) -> &ImageData {
+        &self.image
+    }
+
+    // Returns width of a single frame in pixels
+    pub fn frame_width(&self) -> u32 {
+        self.frame_width
+    }
+
+    // Returns height of a single frame in pixels
+    pub fn frame_height(&self) -> u32 {
+        self.frame_height
+    }
+
+    // Returns number of frame columns in grid
+    pub fn columns(&self) -> u32 {
+        self.columns
+    }
+
+    // Returns number of frame rows in grid
+    pub fn rows(&self) -> u32 {
+        self.rows
+    }
+
+    // Returns total number of frames in grid
+    pub fn frame_count(&self) -> usize {
+        (self.columns * self.rows) as usize
+    }
+
+    // Returns source rectangle of frame at given index
+    pub fn frame(&self, index: usize) -> Option<Rect> {
+        if index >= self.frame_count() {
+            return None;
+        }
+
+        let column = index as u32 % self.columns;
+        let row = index as u32 / self.columns;
+
+        Some(Rect::new(
+            (column * self.frame_width) as i32,
+            (row * self.frame_height) as i32,
+            self.frame_width,
+            self.frame_height,
+        ))
+    }
+
+    // Returns source rectangle of frame at given column and row
+    pub fn frame_at(&self, column: u32, row: u32) -> Option<Rect> {
+        if column >= self.columns || row >= self.rows {
+            return None;
+        }
+
+        self.frame((row * self.columns + column) as usize)
+    }
+}
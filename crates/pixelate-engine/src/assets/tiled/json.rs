@@ -0,0 +1,265 @@
+use super::{
+    decode_tile_data, image_source, property_value, LayerDocument, MapDocument, TileDocument,
+    TilesetDocument, TilesetEntry,
+};
+use crate::tilemap::{MapObject, ObjectShape, Properties, PropertyValue};
+use serde_json::{Map, Value};
+use std::path::Path;
+
+// Reads a .tmj map, leaving external tilesets to be loaded separately
+pub(super) fn parse_map(text: &str, base: &Path) -> Result<MapDocument, String> {
+    let root: Value = serde_json::from_str(text).map_err(|error| error.to_string())?;
+
+    let tilesets = array(&root, "tilesets")
+        .iter()
+        .map(|tileset| {
+            let first_gid = required(tileset, "firstgid")?;
+
+            Ok(match string(tileset, "source") {
+                Some(source) => TilesetEntry::External {
+                    first_gid,
+                    path: base.join(source),
+                },
+                None => TilesetEntry::Embedded(read_tileset(tileset, base, first_gid)?),
+            })
+        })
+        .collect::<Result<_, String>>()?;
+
+    let mut layers = Vec::new();
+    read_layers(array(&root, "layers"), true, &mut layers)?;
+
+    Ok(MapDocument {
+        orientation: string(&root, "orientation")
+            .unwrap_or("orthogonal")
+            .to_string(),
+        infinite: boolean(&root, "infinite").unwrap_or(false),
+        columns: required(&root, "width")?,
+        rows: required(&root, "height")?,
+        tile_width: required(&root, "tilewidth")?,
+        tile_height: required(&root, "tileheight")?,
+        tilesets,
+        layers,
+        properties: read_properties(&root)?,
+    })
+}
+
+// Reads a .tsj tileset referenced by a map
+pub(super) fn parse_tileset(
+    text: &str,
+    base: &Path,
+    first_gid: u32,
+) -> Result<TilesetDocument, String> {
+    let root: Value = serde_json::from_str(text).map_err(|error| error.to_string())?;
+
+    read_tileset(&root, base, first_gid)
+}
+
+fn read_tileset(tileset: &Value, base: &Path, first_gid: u32) -> Result<TilesetDocument, String> {
+    let tiles = array(tileset, "tiles")
+        .iter()
+        .map(|tile| {
+            let animation = array(tile, "animation")
+                .iter()
+                .map(|frame| Ok((required(frame, "tileid")?, required(frame, "duration")?)))
+                .collect::<Result<_, String>>()?;
+
+            Ok(TileDocument {
+                id: required(tile, "id")?,
+                image: string(tile, "image")
+                    .map(|image| image_source(base, image, None))
+                    .transpose()?,
+                animation,
+            })
+        })
+        .collect::<Result<_, String>>()?;
+
+    Ok(TilesetDocument {
+        first_gid,
+        tile_width: required(tileset, "tilewidth")?,
+        tile_height: required(tileset, "tileheight")?,
+        tile_count: optional(tileset, "tilecount")?.unwrap_or(0),
+        columns: optional(tileset, "columns")?.unwrap_or(0),
+        margin: optional(tileset, "margin")?.unwrap_or(0),
+        spacing: optional(tileset, "spacing")?.unwrap_or(0),
+        image: string(tileset, "image")
+            .map(|image| image_source(base, image, string(tileset, "transparentcolor")))
+            .transpose()?,
+        tiles,
+    })
+}
+
+// Collects tile and object layers in drawing order, flattening groups
+fn read_layers(
+    values: &[Value],
+    visible: bool,
+    layers: &mut Vec<LayerDocument>,
+) -> Result<(), String> {
+    for layer in values {
+        let is_visible = visible && boolean(layer, "visible").unwrap_or(true);
+        let name = string(layer, "name").unwrap_or_default().to_string();
+
+        match string(layer, "type") {
+            Some("tilelayer") => layers.push(LayerDocument::Tiles {
+                gids: read_tile_data(layer, &name)?,
+                name,
+                visible: is_visible,
+            }),
+            Some("objectgroup") => layers.push(LayerDocument::Objects {
+                objects: array(layer, "objects")
+                    .iter()
+                    .map(|object| read_object(object, &name, is_visible))
+                    .collect::<Result<_, String>>()?,
+            }),
+            Some("group") => read_layers(array(layer, "layers"), is_visible, layers)?,
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+fn read_tile_data(layer: &Value, name: &str) -> Result<Vec<u32>, String> {
+    match layer.get("data") {
+        Some(Value::Array(gids)) => gids
+            .iter()
+            .map(|gid| {
+                gid.as_u64()
+                    .and_then(|gid| u32::try_from(gid).ok())
+                    .ok_or_else(|| format!("invalid tile id '{}'", gid))
+            })
+            .collect(),
+        Some(Value::String(text)) => decode_tile_data(
+            text,
+            string(layer, "encoding"),
+            string(layer, "compression"),
+        ),
+        _ => Err(format!("layer '{}' has no tile data", name)),
+    }
+}
+
+fn read_object(
+    value: &Value,
+    layer: &str,
+    layer_visible: bool,
+) -> Result<(MapObject, Option<u32>), String> {
+    let shape = if let Some(points) = value.get("polygon") {
+        ObjectShape::Polygon(read_points(points)?)
+    } else if let Some(points) = value.get("polyline") {
+        ObjectShape::Polyline(read_points(points)?)
+    } else if let Some(text) = value.get("text") {
+        ObjectShape::Text(string(text, "text").unwrap_or_default().to_string())
+    } else if boolean(value, "ellipse").unwrap_or(false) {
+        ObjectShape::Ellipse
+    } else if boolean(value, "point").unwrap_or(false) {
+        ObjectShape::Point
+    } else {
+        ObjectShape::Rectangle
+    };
+
+    let object = MapObject {
+        id: optional(value, "id")?.unwrap_or(0),
+        name: string(value, "name").unwrap_or_default().to_string(),
+        // Tiled 1.9 renamed object type to class
+        class: string(value, "class")
+            .or(string(value, "type"))
+            .unwrap_or_default()
+            .to_string(),
+        layer: layer.to_string(),
+        position: (number(value, "x"), number(value, "y")),
+        size: (number(value, "width"), number(value, "height")),
+        rotation: number(value, "rotation").to_radians(),
+        visible: layer_visible && boolean(value, "visible").unwrap_or(true),
+        shape,
+        tile: None,
+        properties: read_properties(value)?,
+    };
+
+    Ok((object, optional(value, "gid")?))
+}
+
+// Parses a point list written as [{"x": .., "y": ..}, ...]
+fn read_points(points: &Value) -> Result<Vec<(f32, f32)>, String> {
+    points
+        .as_array()
+        .ok_or("point list is not an array")?
+        .iter()
+        .map(|point| Ok((number(point, "x"), number(point, "y"))))
+        .collect()
+}
+
+fn read_properties(value: &Value) -> Result<Properties, String> {
+    let mut properties = Properties::new();
+
+    for property in array(value, "properties") {
+        let name = string(property, "name").ok_or("property has no name")?;
+        let content = property.get("value").unwrap_or(&Value::Null);
+
+        let value = match (string(property, "type").unwrap_or("string"), content) {
+            ("class", Value::Object(members)) => PropertyValue::Class(read_members(members)),
+            ("class", _) => PropertyValue::Class(Properties::new()),
+            (kind, Value::String(text)) => property_value(kind, text)?,
+            (kind, other) => property_value(kind, &other.to_string())?,
+        };
+
+        properties.insert(name.to_string(), value);
+    }
+
+    Ok(properties)
+}
+
+// Converts members of a class property, whose types are only known from their JSON form
+fn read_members(members: &Map<String, Value>) -> Properties {
+    members
+        .iter()
+        .filter_map(|(name, value)| {
+            let value = match value {
+                Value::Bool(value) => PropertyValue::Bool(*value),
+                Value::Number(number) => match number.as_i64() {
+                    Some(value) => PropertyValue::Int(value),
+                    None => PropertyValue::Float(number.as_f64()?),
+                },
+                Value::String(text) => PropertyValue::String(text.clone()),
+                Value::Object(members) => PropertyValue::Class(read_members(members)),
+                _ => return None,
+            };
+
+            Some((name.clone(), value))
+        })
+        .collect()
+}
+
+fn array<'a>(value: &'a Value, key: &str) -> &'a [Value] {
+    value
+        .get(key)
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+}
+
+fn string<'a>(value: &'a Value, key: &str) -> Option<&'a str> {
+    value.get(key).and_then(Value::as_str)
+}
+
+fn boolean(value: &Value, key: &str) -> Option<bool> {
+    value.get(key).and_then(Value::as_bool)
+}
+
+fn number(value: &Value, key: &str) -> f32 {
+    value.get(key).and_then(Value::as_f64).unwrap_or(0.0) as f32
+}
+
+fn optional(value: &Value, key: &str) -> Result<Option<u32>, String> {
+    value
+        .get(key)
+        .map(|field| {
+            field
+                .as_u64()
+                .and_then(|number| u32::try_from(number).ok())
+                .ok_or_else(|| format!("invalid value '{}' for '{}'", field, key))
+        })
+        .transpose()
+}
+
+fn required(value: &Value, key: &str) -> Result<u32, String> {
+    optional(value, key)?.ok_or_else(|| format!("missing '{}'", key))
+}
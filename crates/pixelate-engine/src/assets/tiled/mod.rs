@@ -0,0 +1,701 @@
+mod json;
+mod xml;
+
+use super::{AssetManager, ImageData};
+use crate::{
+    log_warn,
+    tilemap::{MapObject, Properties, PropertyValue, Tile, TileAnimation, TileMap},
+    AssetError,
+};
+use flate2::read::{GzDecoder, ZlibDecoder};
+use std::{
+    collections::HashMap,
+    fs::read_to_string,
+    io::Read,
+    mem,
+    path::{Path, PathBuf},
+};
+
+// Flip flags stored in upper bits of a global tile id
+const FLIP_X: u32 = 1 << 31;
+const FLIP_Y: u32 = 1 << 30;
+const FLIP_DIAGONAL: u32 = 1 << 29;
+
+// Bits of a global tile id left after dropping flip and hexagonal rotation flags
+const GID_MASK: u32 = 0x0FFF_FFFF;
+
+// Number of tile columns in atlas all tilesets are packed into
+const ATLAS_COLUMNS: u32 = 16;
+
+// Map as read from either file format, before tilesets are packed into one atlas
+struct MapDocument {
+    orientation: String,
+    infinite: bool,
+    columns: u32,
+    rows: u32,
+    tile_width: u32,
+    tile_height: u32,
+    tilesets: Vec<TilesetEntry>,
+    layers: Vec<LayerDocument>,
+    properties: Properties,
+}
+
+enum TilesetEntry {
+    Embedded(TilesetDocument),
+    External { first_gid: u32, path: PathBuf },
+}
+
+struct TilesetDocument {
+    first_gid: u32,
+    tile_width: u32,
+    tile_height: u32,
+    tile_count: u32,
+    columns: u32,
+    margin: u32,
+    spacing: u32,
+    image: Option<ImageSource>,
+    tiles: Vec<TileDocument>,
+}
+
+struct ImageSource {
+    path: PathBuf,
+    transparent: Option<[u8; 3]>,
+}
+
+// Per-tile data of a tileset: own image in image collections, and animation frames
+struct TileDocument {
+    id: u32,
+    image: Option<ImageSource>,
+    // Local tile ids paired with their duration in milliseconds
+    animation: Vec<(u32, u32)>,
+}
+
+enum LayerDocument {
+    Tiles {
+        name: String,
+        visible: bool,
+        gids: Vec<u32>,
+    },
+    // Objects paired with global id of tile they show, if any
+    Objects {
+        objects: Vec<(MapObject, Option<u32>)>,
+    },
+}
+
+// Reads a Tiled map in .tmx or .tmj form along with its external tilesets and images
+pub(crate) fn load_map(path: &str) -> Result<TileMap, AssetError> {
+    let text = read_text(Path::new(path))?;
+    let base = Path::new(path).parent().unwrap_or(Path::new(""));
+
+    let mut document = match is_json(Path::new(path)) {
+        true => json::parse_map(&text, base),
+        false => xml::parse_map(&text, base),
+    }
+    .map_err(|message| decode_error(Path::new(path), message))?;
+
+    if document.orientation != "orthogonal" {
+        return Err(decode_error(
+            Path::new(path),
+            format!("{} orientation is not supported", document.orientation),
+        ));
+    }
+
+    if document.infinite {
+        return Err(decode_error(
+            Path::new(path),
+            "infinite maps are not supported",
+        ));
+    }
+
+    let tilesets = mem::take(&mut document.tilesets)
+        .into_iter()
+        .map(|entry| match entry {
+            TilesetEntry::Embedded(tileset) => Ok(tileset),
+            TilesetEntry::External { first_gid, path } => {
+                let text = read_text(&path)?;
+                let base = path.parent().unwrap_or(Path::new(""));
+
+                match is_json(&path) {
+                    true => json::parse_tileset(&text, base, first_gid),
+                    false => xml::parse_tileset(&text, base, first_gid),
+                }
+                .map_err(|message| decode_error(&path, message))
+            }
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    build_map(Path::new(path), document, &tilesets)
+}
+
+// Packs tilesets into one atlas and fills a tile map with layers and objects
+fn build_map(
+    path: &Path,
+    document: MapDocument,
+    tilesets: &[TilesetDocument],
+) -> Result<TileMap, AssetError> {
+    let (tile_width, tile_height) = (document.tile_width, document.tile_height);
+
+    if tile_width == 0 || tile_height == 0 {
+        return Err(decode_error(path, "tile size must not be zero"));
+    }
+
+    // Image and top-left pixel of every tile, in atlas order
+    let mut images = Vec::new();
+    let mut cells: Vec<(usize, u32, u32)> = Vec::new();
+    let mut atlas_indices: HashMap<u32, u32> = HashMap::new();
+
+    // Tiles of another size than map grid, such as trees or doors placed as objects; they keep
+    // their own image, and only object layers may use them
+    let mut own_images: Vec<(u32, ImageData)> = Vec::new();
+    let fits_grid = |width: u32, height: u32| (width, height) == (tile_width, tile_height);
+
+    for tileset in tilesets {
+        if let Some(source) = &tileset.image {
+            let image = load_image(source)?;
+            let (width, height) = (tileset.tile_width, tileset.tile_height);
+            let stride = (width + tileset.spacing, height + tileset.spacing);
+
+            let columns = match (tileset.columns, stride.0) {
+                (_, 0) => 0,
+                (0, _) => {
+                    (image.width + tileset.spacing).saturating_sub(tileset.margin * 2) / stride.0
+                }
+                (columns, _) => columns,
+            };
+
+            let tile_count = match (columns, tileset.tile_count) {
+                (0, _) => 0,
+                (_, 0) if stride.1 == 0 => 0,
+                (_, 0) => {
+                    let rows = (image.height + tileset.spacing).saturating_sub(tileset.margin * 2)
+                        / stride.1;
+                    columns * rows
+                }
+                (_, tile_count) => tile_count,
+            };
+
+            for id in 0..tile_count {
+                let x = tileset.margin + (id % columns) * stride.0;
+                let y = tileset.margin + (id / columns) * stride.1;
+
+                if fits_grid(width, height) {
+                    atlas_indices.insert(tileset.first_gid + id, cells.len() as u32);
+                    cells.push((images.len(), x, y));
+                } else {
+                    own_images.push((tileset.first_gid + id, crop(&image, x, y, width, height)));
+                }
+            }
+
+            images.push(image);
+        }
+
+        for tile in &tileset.tiles {
+            if let Some(source) = &tile.image {
+                let image = load_image(source)?;
+
+                if fits_grid(image.width, image.height) {
+                    images.push(image);
+                    atlas_indices.insert(tileset.first_gid + tile.id, cells.len() as u32);
+                    cells.push((images.len() - 1, 0, 0));
+                } else {
+                    own_images.push((tileset.first_gid + tile.id, image));
+                }
+            }
+        }
+    }
+
+    let atlas = pack_atlas(&images, &cells, tile_width, tile_height);
+
+    let mut map = TileMap::new(
+        atlas,
+        tile_width,
+        tile_height,
+        document.columns,
+        document.rows,
+    )
+    .ok_or_else(|| decode_error(path, "tileset atlas is smaller than one tile"))?;
+
+    let mut own_sizes: HashMap<u32, (u32, u32)> = HashMap::new();
+
+    for (gid, image) in own_images {
+        own_sizes.insert(gid, (image.width, image.height));
+        atlas_indices.insert(gid, map.add_tile_image(image));
+    }
+
+    for tileset in tilesets {
+        for tile in tileset
+            .tiles
+            .iter()
+            .filter(|tile| !tile.animation.is_empty())
+        {
+            let Some(&index) = atlas_indices.get(&(tileset.first_gid + tile.id)) else {
+                continue;
+            };
+
+            let frames = tile
+                .animation
+                .iter()
+                .filter_map(|&(id, duration)| {
+                    let frame = atlas_indices.get(&(tileset.first_gid + id))?;
+                    Some((*frame, duration as f32 / 1000.0))
+                })
+                .collect();
+
+            if let Some(animation) = TileAnimation::new(frames) {
+                map.set_animation(index, animation);
+            }
+        }
+    }
+
+    let mut missing_tiles = 0;
+
+    let mut resolve = |gid: u32| {
+        if gid & GID_MASK == 0 {
+            return None;
+        }
+
+        let Some(&index) = atlas_indices.get(&(gid & GID_MASK)) else {
+            missing_tiles += 1;
+            return None;
+        };
+
+        Some(
+            Tile::new(index)
+                .flip_x(gid & FLIP_X != 0)
+                .flip_y(gid & FLIP_Y != 0)
+                .flip_diagonal(gid & FLIP_DIAGONAL != 0),
+        )
+    };
+
+    // Swapping axes of non-square tiles would drop pixels, so layers refuse such tiles
+    let has_diagonal_flips = document.layers.iter().any(|layer| match layer {
+        LayerDocument::Tiles { gids, .. } => gids.iter().any(|gid| gid & FLIP_DIAGONAL != 0),
+        LayerDocument::Objects { .. } => false,
+    });
+
+    if has_diagonal_flips && tile_width != tile_height {
+        return Err(decode_error(
+            path,
+            format!(
+                "diagonally flipped tiles need square tiles, but map uses {}x{}",
+                tile_width, tile_height
+            ),
+        ));
+    }
+
+    let cell_count = (document.columns * document.rows) as usize;
+
+    for layer in document.layers {
+        match layer {
+            LayerDocument::Tiles {
+                name,
+                visible,
+                gids,
+            } => {
+                if gids.len() != cell_count {
+                    return Err(decode_error(
+                        path,
+                        format!(
+                            "layer '{}' has {} tiles, expected {}",
+                            name,
+                            gids.len(),
+                            cell_count
+                        ),
+                    ));
+                }
+
+                // Layers are drawn on map grid, so their tiles must fill exactly one cell
+                if let Some((gid, (width, height))) = gids
+                    .iter()
+                    .find_map(|gid| Some((gid & GID_MASK, *own_sizes.get(&(gid & GID_MASK))?)))
+                {
+                    return Err(decode_error(
+                        path,
+                        format!(
+                            "layer '{}' uses {}x{} tile {} but map uses {}x{} tiles",
+                            name, width, height, gid, tile_width, tile_height
+                        ),
+                    ));
+                }
+
+                let index = map.add_layer(&name);
+
+                if let Some(target) = map.layer_mut(index) {
+                    target.set_visible(visible);
+
+                    for (cell, &gid) in gids.iter().enumerate() {
+                        let (column, row) = (
+                            cell as u32 % document.columns,
+                            cell as u32 / document.columns,
+                        );
+                        target.set_tile(column, row, resolve(gid));
+                    }
+                }
+            }
+            LayerDocument::Objects { objects } => {
+                for (mut object, gid) in objects {
+                    object.tile = gid.and_then(&mut resolve);
+                    map.add_object(object);
+                }
+            }
+        }
+    }
+
+    if missing_tiles > 0 {
+        log_warn!(
+            "Assets",
+            "Map '{}' uses {} tiles not found in any tileset",
+            path.display(),
+            missing_tiles
+        );
+    }
+
+    for (name, value) in document.properties {
+        map.set_property(&name, value);
+    }
+
+    Ok(map)
+}
+
+// Copies every tile into a grid image of fixed column count
+fn pack_atlas(
+    images: &[ImageData],
+    cells: &[(usize, u32, u32)],
+    tile_width: u32,
+    tile_height: u32,
+) -> ImageData {
+    let rows = (cells.len() as u32).div_ceil(ATLAS_COLUMNS).max(1);
+    let width = ATLAS_COLUMNS * tile_width;
+    let mut rgba_bytes = vec![0; (width * rows * tile_height * 4) as usize];
+
+    for (index, &(image, source_x, source_y)) in cells.iter().enumerate() {
+        let origin_x = index as u32 % ATLAS_COLUMNS * tile_width;
+        let origin_y = index as u32 / ATLAS_COLUMNS * tile_height;
+
+        for y in 0..tile_height {
+            for x in 0..tile_width {
+                let source = images[image].pixel((source_x + x) as i32, (source_y + y) as i32);
+
+                if let Some(rgba) = source {
+                    let offset = (((origin_y + y) * width + origin_x + x) * 4) as usize;
+                    rgba_bytes[offset..offset + 4].copy_from_slice(&rgba);
+                }
+            }
+        }
+    }
+
+    ImageData::from_bytes(rgba_bytes, width, rows * tile_height)
+}
+
+// Copies a rectangle out of an image, leaving parts outside image transparent
+fn crop(image: &ImageData, x: u32, y: u32, width: u32, height: u32) -> ImageData {
+    let mut rgba_bytes = Vec::with_capacity((width * height * 4) as usize);
+
+    for row in y..y + height {
+        for column in x..x + width {
+            let rgba = image.pixel(column as i32, row as i32).unwrap_or_default();
+            rgba_bytes.extend_from_slice(&rgba);
+        }
+    }
+
+    ImageData::from_bytes(rgba_bytes, width, height)
+}
+
+// Decodes a tileset image, clearing pixels of its transparent color
+fn load_image(source: &ImageSource) -> Result<ImageData, AssetError> {
+    let mut image = AssetManager::decode_image(&source.path.to_string_lossy())?;
+
+    if let Some(color) = source.transparent {
+        for rgba in image.rgba_bytes.chunks_exact_mut(4) {
+            if rgba[..3] == color {
+                rgba[3] = 0;
+            }
+        }
+    }
+
+    Ok(image)
+}
+
+// Builds an image reference relative to file it appears in
+fn image_source(base: &Path, path: &str, transparent: Option<&str>) -> Result<ImageSource, String> {
+    let transparent = transparent
+        .map(|text| {
+            parse_color(text)
+                .map(|[red, green, blue, _]| [red, green, blue])
+                .ok_or_else(|| format!("invalid transparent color '{}'", text))
+        })
+        .transpose()?;
+
+    Ok(ImageSource {
+        path: base.join(path),
+        transparent,
+    })
+}
+
+// Decodes tile layer data stored as CSV, or as base64 with optional zlib or gzip compression
+fn decode_tile_data(
+    text: &str,
+    encoding: Option<&str>,
+    compression: Option<&str>,
+) -> Result<Vec<u32>, String> {
+    match encoding {
+        Some("csv") => text
+            .split(',')
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(|value| {
+                value
+                    .parse()
+                    .map_err(|_| format!("invalid tile id '{}'", value))
+            })
+            .collect(),
+        Some("base64") => {
+            let bytes = decode_base64(text)?;
+
+            let bytes = match compression.unwrap_or_default() {
+                "" => bytes,
+                "zlib" => decompress(ZlibDecoder::new(bytes.as_slice()))?,
+                "gzip" => decompress(GzDecoder::new(bytes.as_slice()))?,
+                other => return Err(format!("{} compression is not supported", other)),
+            };
+
+            if bytes.len() % 4 != 0 {
+                return Err("tile data length is not a multiple of 4 bytes".to_string());
+            }
+
+            Ok(bytes
+                .chunks_exact(4)
+                .map(|gid| u32::from_le_bytes([gid[0], gid[1], gid[2], gid[3]]))
+                .collect())
+        }
+        Some(other) => Err(format!("{} encoding is not supported", other)),
+        None => Err("tile data has no encoding".to_string()),
+    }
+}
+
+// Decodes standard base64, ignoring whitespace and stopping at padding
+fn decode_base64(text: &str) -> Result<Vec<u8>, String> {
+    let mut bytes = Vec::with_capacity(text.len() * 3 / 4);
+    let (mut buffer, mut bits) = (0u32, 0);
+
+    for character in text
+        .bytes()
+        .filter(|character| !character.is_ascii_whitespace())
+    {
+        let value = match character {
+            b'A'..=b'Z' => character - b'A',
+            b'a'..=b'z' => character - b'a' + 26,
+            b'0'..=b'9' => character - b'0' + 52,
+            b'+' => 62,
+            b'/' => 63,
+            b'=' => break,
+            _ => return Err(format!("invalid base64 character '{}'", character as char)),
+        };
+
+        buffer = (buffer << 6 | value as u32) & 0xFFFF;
+        bits += 6;
+
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((buffer >> bits) as u8);
+        }
+    }
+
+    Ok(bytes)
+}
+
+// Reads a whole compressed stream
+fn decompress(mut decoder: impl Read) -> Result<Vec<u8>, String> {
+    let mut bytes = Vec::new();
+
+    decoder
+        .read_to_end(&mut bytes)
+        .map_err(|error| format!("failed to decompress tile data: {}", error))?;
+
+    Ok(bytes)
+}
+
+// Converts a property stored as text to a value of its declared type
+fn property_value(kind: &str, text: &str) -> Result<PropertyValue, String> {
+    let invalid = || format!("invalid {} property value '{}'", kind, text);
+
+    Ok(match kind {
+        "bool" => PropertyValue::Bool(text == "true"),
+        "int" => PropertyValue::Int(text.parse().map_err(|_| invalid())?),
+        "float" => PropertyValue::Float(text.parse().map_err(|_| invalid())?),
+        // Unset colors are written as an empty string
+        "color" if text.is_empty() => PropertyValue::Color([0, 0, 0, 0]),
+        "color" => PropertyValue::Color(parse_color(text).ok_or_else(invalid)?),
+        "file" => PropertyValue::File(text.to_string()),
+        "object" => PropertyValue::Object(text.parse().map_err(|_| invalid())?),
+        _ => PropertyValue::String(text.to_string()),
+    })
+}
+
+// Parses #AARRGGBB or #RRGGBB, with or without leading '#'
+fn parse_color(text: &str) -> Option<[u8; 4]> {
+    let digits = text.strip_prefix('#').unwrap_or(text);
+    let value = u32::from_str_radix(digits, 16).ok()?;
+    let [alpha, red, green, blue] = value.to_be_bytes();
+
+    match digits.len() {
+        6 => Some([red, green, blue, 255]),
+        8 => Some([red, green, blue, alpha]),
+        _ => None,
+    }
+}
+
+// Checks if a map or tileset file uses JSON form
+fn is_json(path: &Path) -> bool {
+    path.extension().is_some_and(|extension| {
+        ["tmj", "tsj", "json"]
+            .iter()
+            .any(|json| extension.eq_ignore_ascii_case(json))
+    })
+}
+
+fn read_text(path: &Path) -> Result<String, AssetError> {
+    read_to_string(path).map_err(|error| AssetError::FileRead {
+        path: path.display().to_string(),
+        source: error,
+    })
+}
+
+fn decode_error(path: &Path, message: impl Into<String>) -> AssetError {
+    AssetError::MapDecode {
+        path: path.display().to_string(),
+        message: message.into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::renderer::Rect;
+
+    const RED: [u8; 4] = [255, 0, 0, 255];
+    const GREEN: [u8; 4] = [0, 255, 0, 255];
+    const BLUE: [u8; 4] = [0, 0, 255, 255];
+    const YELLOW: [u8; 4] = [255, 255, 0, 255];
+
+    fn fixture(name: &str) -> Result<TileMap, AssetError> {
+        load_map(&format!("tests/fixtures/tiled/{}", name))
+    }
+
+    // Returns top-left pixel of tile at given column and row of first layer, after flips
+    fn corner(map: &TileMap, column: u32, row: u32, x: u32, y: u32) -> Option<[u8; 4]> {
+        let tile = map.layer(0)?.tile(column, row)?;
+
+        map.tile_pixel(&tile, tile.index, x, y)
+    }
+
+    fn decode_message(result: Result<TileMap, AssetError>) -> String {
+        match result {
+            Err(AssetError::MapDecode { message, .. }) => message,
+            Err(error) => panic!("expected a decode error, got {}", error),
+            Ok(_) => panic!("expected a decode error"),
+        }
+    }
+
+    #[test]
+    fn csv_and_json_maps_read_flip_flags_from_external_tilesets() {
+        for name in ["csv.tmx", "map.tmj"] {
+            let map = fixture(name).unwrap();
+            let layer = map.layer(0).unwrap();
+
+            assert_eq!((map.columns(), map.rows()), (2, 2));
+            assert_eq!(layer.tile(0, 0), Some(Tile::new(0)));
+            assert_eq!(layer.tile(1, 0), Some(Tile::new(0).flip_x(true)));
+            assert_eq!(layer.tile(0, 1), Some(Tile::new(0).flip_y(true)));
+            assert_eq!(layer.tile(1, 1), Some(Tile::new(0).flip_diagonal(true)));
+
+            assert_eq!(corner(&map, 0, 0, 0, 0), Some(RED));
+            assert_eq!(corner(&map, 1, 0, 0, 0), Some(GREEN));
+            assert_eq!(corner(&map, 0, 1, 0, 0), Some(BLUE));
+            assert_eq!(corner(&map, 1, 1, 1, 0), Some(BLUE));
+        }
+    }
+
+    #[test]
+    fn compressed_base64_data_decodes_to_same_tiles() {
+        for name in ["zlib.tmx", "gzip.tmx"] {
+            let map = fixture(name).unwrap();
+
+            assert_eq!(corner(&map, 0, 0, 0, 0), Some(YELLOW));
+            assert_eq!(corner(&map, 1, 0, 0, 0), Some(RED));
+            assert_eq!(corner(&map, 0, 1, 0, 0), Some(RED));
+            assert_eq!(corner(&map, 1, 1, 0, 0), Some(YELLOW));
+        }
+    }
+
+    #[test]
+    fn object_and_map_properties_keep_their_types() {
+        for name in ["csv.tmx", "map.tmj"] {
+            let map = fixture(name).unwrap();
+            let spawn = map.object_by_name("spawn").unwrap();
+
+            assert_eq!(
+                map.property("music"),
+                Some(&PropertyValue::String("theme.ogg".to_string()))
+            );
+            assert_eq!((spawn.id, spawn.class.as_str()), (1, "player"));
+            assert_eq!(spawn.layer, "spawns");
+            assert_eq!((spawn.position, spawn.size), ((2.0, 4.0), (2.0, 2.0)));
+
+            let property = |name: &str| spawn.properties.get(name).cloned();
+            assert_eq!(property("health"), Some(PropertyValue::Int(3)));
+            assert_eq!(property("speed"), Some(PropertyValue::Float(1.5)));
+            assert_eq!(property("friendly"), Some(PropertyValue::Bool(true)));
+            assert_eq!(
+                property("tint"),
+                Some(PropertyValue::Color([255, 0, 0, 128]))
+            );
+            assert_eq!(
+                property("greeting"),
+                Some(PropertyValue::String("hello".to_string()))
+            );
+        }
+    }
+
+    #[test]
+    fn object_tiles_keep_their_own_size() {
+        let map = fixture("objects.tmx").unwrap();
+        let grass = map.layer(0).unwrap().tile(1, 1).unwrap();
+        let tree = map.object_by_name("tree").unwrap().tile.unwrap();
+
+        assert_eq!(
+            map.tile_source(grass.index).unwrap().1,
+            Rect::new(0, 0, 16, 16)
+        );
+        assert_eq!(
+            map.tile_pixel(&grass, grass.index, 15, 15),
+            Some([80, 200, 80, 255])
+        );
+
+        let (image, frame) = map.tile_source(tree.index).unwrap();
+        assert_eq!((image.width, image.height), (16, 32));
+        assert_eq!(frame, Rect::new(0, 0, 16, 32));
+        assert_eq!(
+            map.tile_pixel(&tree, tree.index, 0, 31),
+            Some([120, 70, 20, 255])
+        );
+    }
+
+    #[test]
+    fn layer_tiles_of_another_size_are_rejected() {
+        assert!(decode_message(fixture("mismatched.tmx")).contains("uses 1x1 tile 1"));
+        assert!(decode_message(fixture("collection.tmx")).contains("uses 2x3 tile 1"));
+        assert!(decode_message(fixture("diagonal.tmx")).contains("square tiles"));
+    }
+
+    #[test]
+    fn tile_data_rejects_unknown_encodings() {
+        assert_eq!(
+            decode_tile_data("1, 2,\n3", Some("csv"), None),
+            Ok(vec![1, 2, 3])
+        );
+        assert!(decode_tile_data("AQAAAA==", Some("base64"), Some("zstd")).is_err());
+        assert!(decode_tile_data("1", Some("hex"), None).is_err());
+        assert!(decode_tile_data("AQAA", Some("base64"), None).is_err());
+    }
+}
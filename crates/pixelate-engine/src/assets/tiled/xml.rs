@@ -0,0 +1,279 @@
+use super::{
+    decode_tile_data, image_source, property_value, ImageSource, LayerDocument, MapDocument,
+    TileDocument, TilesetDocument, TilesetEntry,
+};
+use crate::tilemap::{MapObject, ObjectShape, Properties, PropertyValue};
+use roxmltree::{Document, Node};
+use std::{path::Path, str::FromStr};
+
+// Reads a .tmx map, leaving external tilesets to be loaded separately
+pub(super) fn parse_map(text: &str, base: &Path) -> Result<MapDocument, String> {
+    let document = Document::parse(text).map_err(|error| error.to_string())?;
+    let root = document.root_element();
+
+    if !root.has_tag_name("map") {
+        return Err("root element is not <map>".to_string());
+    }
+
+    let tilesets = elements(root, "tileset")
+        .map(|node| {
+            let first_gid = required(node, "firstgid")?;
+
+            Ok(match node.attribute("source") {
+                Some(source) => TilesetEntry::External {
+                    first_gid,
+                    path: base.join(source),
+                },
+                None => TilesetEntry::Embedded(read_tileset(node, base, first_gid)?),
+            })
+        })
+        .collect::<Result<_, String>>()?;
+
+    let mut layers = Vec::new();
+    read_layers(root, true, &mut layers)?;
+
+    Ok(MapDocument {
+        orientation: root
+            .attribute("orientation")
+            .unwrap_or("orthogonal")
+            .to_string(),
+        infinite: root.attribute("infinite") == Some("1"),
+        columns: required(root, "width")?,
+        rows: required(root, "height")?,
+        tile_width: required(root, "tilewidth")?,
+        tile_height: required(root, "tileheight")?,
+        tilesets,
+        layers,
+        properties: read_properties(root)?,
+    })
+}
+
+// Reads a .tsx tileset referenced by a map
+pub(super) fn parse_tileset(
+    text: &str,
+    base: &Path,
+    first_gid: u32,
+) -> Result<TilesetDocument, String> {
+    let document = Document::parse(text).map_err(|error| error.to_string())?;
+    let root = document.root_element();
+
+    if !root.has_tag_name("tileset") {
+        return Err("root element is not <tileset>".to_string());
+    }
+
+    read_tileset(root, base, first_gid)
+}
+
+fn read_tileset(node: Node, base: &Path, first_gid: u32) -> Result<TilesetDocument, String> {
+    let tiles = elements(node, "tile")
+        .map(|tile| {
+            let animation = match element(tile, "animation") {
+                Some(animation) => elements(animation, "frame")
+                    .map(|frame| Ok((required(frame, "tileid")?, required(frame, "duration")?)))
+                    .collect::<Result<_, String>>()?,
+                None => Vec::new(),
+            };
+
+            Ok(TileDocument {
+                id: required(tile, "id")?,
+                image: element(tile, "image")
+                    .map(|image| read_image(image, base))
+                    .transpose()?,
+                animation,
+            })
+        })
+        .collect::<Result<_, String>>()?;
+
+    Ok(TilesetDocument {
+        first_gid,
+        tile_width: required(node, "tilewidth")?,
+        tile_height: required(node, "tileheight")?,
+        tile_count: optional(node, "tilecount")?.unwrap_or(0),
+        columns: optional(node, "columns")?.unwrap_or(0),
+        margin: optional(node, "margin")?.unwrap_or(0),
+        spacing: optional(node, "spacing")?.unwrap_or(0),
+        image: element(node, "image")
+            .map(|image| read_image(image, base))
+            .transpose()?,
+        tiles,
+    })
+}
+
+fn read_image(node: Node, base: &Path) -> Result<ImageSource, String> {
+    let source = node
+        .attribute("source")
+        .ok_or("<image> is missing attribute 'source'")?;
+
+    image_source(base, source, node.attribute("trans"))
+}
+
+// Collects tile and object layers in drawing order, flattening groups
+fn read_layers(node: Node, visible: bool, layers: &mut Vec<LayerDocument>) -> Result<(), String> {
+    for child in node.children().filter(Node::is_element) {
+        let is_visible = visible && child.attribute("visible") != Some("0");
+        let name = child.attribute("name").unwrap_or_default().to_string();
+
+        match child.tag_name().name() {
+            "layer" => layers.push(LayerDocument::Tiles {
+                gids: read_tile_data(child)?,
+                name,
+                visible: is_visible,
+            }),
+            "objectgroup" => layers.push(LayerDocument::Objects {
+                objects: elements(child, "object")
+                    .map(|object| read_object(object, &name, is_visible))
+                    .collect::<Result<_, String>>()?,
+            }),
+            "group" => read_layers(child, is_visible, layers)?,
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+fn read_tile_data(layer: Node) -> Result<Vec<u32>, String> {
+    let data = element(layer, "data").ok_or("<layer> has no <data>")?;
+
+    match data.attribute("encoding") {
+        None => elements(data, "tile")
+            .map(|tile| Ok(optional(tile, "gid")?.unwrap_or(0)))
+            .collect(),
+        encoding => decode_tile_data(
+            data.text().unwrap_or_default(),
+            encoding,
+            data.attribute("compression"),
+        ),
+    }
+}
+
+fn read_object(
+    node: Node,
+    layer: &str,
+    layer_visible: bool,
+) -> Result<(MapObject, Option<u32>), String> {
+    let shape = if let Some(polygon) = element(node, "polygon") {
+        ObjectShape::Polygon(read_points(polygon)?)
+    } else if let Some(polyline) = element(node, "polyline") {
+        ObjectShape::Polyline(read_points(polyline)?)
+    } else if let Some(text) = element(node, "text") {
+        ObjectShape::Text(text.text().unwrap_or_default().to_string())
+    } else if element(node, "ellipse").is_some() {
+        ObjectShape::Ellipse
+    } else if element(node, "point").is_some() {
+        ObjectShape::Point
+    } else {
+        ObjectShape::Rectangle
+    };
+
+    let object = MapObject {
+        id: optional(node, "id")?.unwrap_or(0),
+        name: node.attribute("name").unwrap_or_default().to_string(),
+        // Tiled 1.9 renamed object type to class
+        class: node
+            .attribute("class")
+            .or(node.attribute("type"))
+            .unwrap_or_default()
+            .to_string(),
+        layer: layer.to_string(),
+        position: (
+            optional(node, "x")?.unwrap_or(0.0),
+            optional(node, "y")?.unwrap_or(0.0),
+        ),
+        size: (
+            optional(node, "width")?.unwrap_or(0.0),
+            optional(node, "height")?.unwrap_or(0.0),
+        ),
+        rotation: optional::<f32>(node, "rotation")?
+            .unwrap_or(0.0)
+            .to_radians(),
+        visible: layer_visible && node.attribute("visible") != Some("0"),
+        shape,
+        tile: None,
+        properties: read_properties(node)?,
+    };
+
+    Ok((object, optional(node, "gid")?))
+}
+
+// Parses a point list written as "x,y x,y ..."
+fn read_points(node: Node) -> Result<Vec<(f32, f32)>, String> {
+    let points = node.attribute("points").unwrap_or_default();
+
+    points
+        .split_whitespace()
+        .map(|point| {
+            point
+                .split_once(',')
+                .and_then(|(x, y)| Some((x.parse().ok()?, y.parse().ok()?)))
+                .ok_or_else(|| format!("invalid point '{}'", point))
+        })
+        .collect()
+}
+
+fn read_properties(node: Node) -> Result<Properties, String> {
+    let mut properties = Properties::new();
+
+    let Some(list) = element(node, "properties") else {
+        return Ok(properties);
+    };
+
+    for property in elements(list, "property") {
+        let name = property
+            .attribute("name")
+            .ok_or("<property> is missing attribute 'name'")?;
+
+        let value = match property.attribute("type").unwrap_or("string") {
+            "class" => PropertyValue::Class(read_properties(property)?),
+            // Multi-line strings are stored as element text instead of an attribute
+            kind => property_value(
+                kind,
+                property
+                    .attribute("value")
+                    .or(property.text())
+                    .unwrap_or_default(),
+            )?,
+        };
+
+        properties.insert(name.to_string(), value);
+    }
+
+    Ok(properties)
+}
+
+fn element<'a, 'input>(node: Node<'a, 'input>, name: &str) -> Option<Node<'a, 'input>> {
+    node.children().find(|child| child.has_tag_name(name))
+}
+
+fn elements<'a, 'input: 'a>(
+    node: Node<'a, 'input>,
+    name: &'a str,
+) -> impl Iterator<Item = Node<'a, 'input>> + 'a {
+    node.children()
+        .filter(move |child| child.has_tag_name(name))
+}
+
+fn optional<T: FromStr>(node: Node, name: &str) -> Result<Option<T>, String> {
+    node.attribute(name)
+        .map(|value| {
+            value.parse().map_err(|_| {
+                format!(
+                    "invalid value '{}' for attribute '{}' of <{}>",
+                    value,
+                    name,
+                    node.tag_name().name()
+                )
+            })
+        })
+        .transpose()
+}
+
+fn required<T: FromStr>(node: Node, name: &str) -> Result<T, String> {
+    optional(node, name)?.ok_or_else(|| {
+        format!(
+            "<{}> is missing attribute '{}'",
+            node.tag_name().name(),
+            name
+        )
+    })
+}
@@ -1,5 +1,6 @@
 use super::{KeyCode, MouseButton};
 
+#[derive(Clone, Debug, PartialEq)]
 pub enum Event {
     KeyPressed(KeyCode),
     KeyReleased(KeyCode),
@@ -1,4 +1,4 @@
-use super::{KeyCode, MouseButton};
+use super::{Event, KeyCode, MouseButton};
 use std::collections::HashSet;
 
 pub struct Input {
@@ -54,4 +54,22 @@ impl Input {
     pub(crate) fn set_mouse_position(&mut self, x: f32, y: f32) {
         self.mouse_position = (x, y);
     }
+
+    // Internal: Updates input state from a discrete event
+    pub(crate) fn apply_event(&mut self, event: &Event) {
+        match *event {
+            Event::KeyPressed(key) => self.set_key_pressed(key, true),
+            Event::KeyReleased(key) => self.set_key_pressed(key, false),
+            Event::MousePressed(button) => self.set_mouse_button_pressed(button, true),
+            Event::MouseReleased(button) => self.set_mouse_button_pressed(button, false),
+            Event::MouseMoved { x, y } => self.set_mouse_position(x, y),
+            Event::WindowResized { .. } => {}
+        }
+    }
+}
+
+impl Default for Input {
+    fn default() -> Self {
+        Self::new()
+    }
 }
@@ -1,6 +1,6 @@
 use winit::keyboard::KeyCode as WinitKeyCode;
 
-#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum KeyCode {
     A,
     B,
@@ -1,6 +1,6 @@
 use winit::event::MouseButton as WinitMouseButton;
 
-#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum MouseButton {
     Left,
     Middle,
@@ -1,14 +1,29 @@
+#![allow(clippy::module_inception)]
+
 pub mod application;
 pub mod assets;
 pub mod input;
 pub mod logger;
 pub mod prelude;
 pub mod renderer;
+pub mod testing;
+pub mod tilemap;
 pub mod window;
 
-pub use application::{Application, Engine};
-pub use assets::{AssetError, AssetManager, ImageData};
+pub use application::{Application, Engine, HeadlessConfiguration, InputScript};
+pub use assets::{
+    AssetError, AssetManager, BitmapFont, Font, ImageData, IndexedImage, Palette, SpriteSheet,
+    TextAlign,
+};
 pub use input::{Event, Input, KeyCode, MouseButton};
 pub use logger::LogLevel;
-pub use renderer::{Renderer, RendererError};
-pub use window::{Window, WindowConfiguration, WindowError};
+pub use renderer::{
+    BlendMode, Bloom, Camera2D, Canvas, ChromaticAberration, ColorLut, Crt, DeferredFrame,
+    DrawParams, Grayscale, IndexedCanvas, Insets, LineCap, LineStyle, PostEffect,
+    RecordingConfiguration, RecordingFormat, Rect, Renderer, RendererError, Scanlines, Sepia,
+    SliceFill,
+};
+pub use tilemap::{
+    MapObject, ObjectShape, Properties, PropertyValue, Tile, TileAnimation, TileLayer, TileMap,
+};
+pub use window::{RedrawMode, Window, WindowConfiguration, WindowError};
@@ -1,8 +1,19 @@
-pub use crate::application::{Application, Engine};
-pub use crate::assets::{AssetError, AssetManager, ImageData};
+pub use crate::application::{Application, Engine, HeadlessConfiguration, InputScript};
+pub use crate::assets::{
+    AssetError, AssetManager, BitmapFont, Font, ImageData, IndexedImage, Palette, SpriteSheet,
+    TextAlign,
+};
 pub use crate::input::{Event, Input, KeyCode, MouseButton};
 pub use crate::logger::LogLevel;
-pub use crate::renderer::{Renderer, RendererError};
-pub use crate::window::{Window, WindowConfiguration, WindowError};
+pub use crate::renderer::{
+    BlendMode, Bloom, Camera2D, Canvas, ChromaticAberration, ColorLut, Crt, DeferredFrame,
+    DrawParams, Grayscale, IndexedCanvas, Insets, LineCap, LineStyle, PostEffect,
+    RecordingConfiguration, RecordingFormat, Rect, Renderer, RendererError, Scanlines, Sepia,
+    SliceFill,
+};
+pub use crate::tilemap::{
+    MapObject, ObjectShape, Properties, PropertyValue, Tile, TileAnimation, TileLayer, TileMap,
+};
+pub use crate::window::{RedrawMode, Window, WindowConfiguration, WindowError};
 
 pub use crate::{log_critical, log_debug, log_error, log_info, log_warn};
@@ -0,0 +1,233 @@
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BlendMode {
+    // Overwrites destination with source color, including alpha
+    Replace,
+    // Composites source over destination using source alpha
+    #[default]
+    Alpha,
+    // Adds source color scaled by its alpha onto destination
+    Additive,
+    // Multiplies destination by source color scaled by its alpha
+    Multiply,
+}
+
+impl BlendMode {
+    // Blends an RGBA source color onto a packed ARGB destination pixel
+    pub(crate) fn blend(self, destination: u32, source: [u8; 4]) -> u32 {
+        let [source_r, source_g, source_b, source_a] = source;
+
+        match self {
+            BlendMode::Replace => pack(source_r, source_g, source_b, source_a),
+            _ if source_a == 0 => destination,
+            BlendMode::Alpha if source_a == 255 => pack(source_r, source_g, source_b, 255),
+            BlendMode::Alpha => {
+                let [destination_r, destination_g, destination_b, destination_a] =
+                    unpack(destination);
+
+                // Over an opaque destination output stays opaque, so divisor is a constant
+                if destination_a == 255 {
+                    let source_a = source_a as u32;
+                    let mix = |source: u8, destination: u8| {
+                        ((source as u32 * source_a + destination as u32 * (255 - source_a)) / 255)
+                            as u8
+                    };
+
+                    return pack(
+                        mix(source_r, destination_r),
+                        mix(source_g, destination_g),
+                        mix(source_b, destination_b),
+                        255,
+                    );
+                }
+
+                // Porter-Duff source-over with straight (non-premultiplied) alpha
+                let source_a = source_a as u32;
+                let destination_weight = destination_a as u32 * (255 - source_a) / 255;
+                let output_a = source_a + destination_weight;
+
+                let mix = |source: u8, destination: u8| {
+                    ((source as u32 * source_a + destination as u32 * destination_weight)
+                        / output_a) as u8
+                };
+
+                pack(
+                    mix(source_r, destination_r),
+                    mix(source_g, destination_g),
+                    mix(source_b, destination_b),
+                    output_a as u8,
+                )
+            }
+            BlendMode::Additive => {
+                let [destination_r, destination_g, destination_b, destination_a] =
+                    unpack(destination);
+                let source_a = source_a as u32;
+
+                let add = |source: u8, destination: u8| {
+                    (destination as u32 + source as u32 * source_a / 255).min(255) as u8
+                };
+
+                pack(
+                    add(source_r, destination_r),
+                    add(source_g, destination_g),
+                    add(source_b, destination_b),
+                    destination_a.max(source_a as u8),
+                )
+            }
+            BlendMode::Multiply => {
+                let [destination_r, destination_g, destination_b, destination_a] =
+                    unpack(destination);
+                let source_a = source_a as u32;
+
+                // Source alpha fades the multiplier towards white (no change)
+                let multiply = |source: u8, destination: u8| {
+                    let factor = (source as u32 * source_a + 255 * (255 - source_a)) / 255;
+                    (destination as u32 * factor / 255) as u8
+                };
+
+                pack(
+                    multiply(source_r, destination_r),
+                    multiply(source_g, destination_g),
+                    multiply(source_b, destination_b),
+                    destination_a,
+                )
+            }
+        }
+    }
+}
+
+// Packs RGBA channels into ARGB u32 format used by buffers
+pub(crate) fn pack(r: u8, g: u8, b: u8, a: u8) -> u32 {
+    ((a as u32) << 24) | ((r as u32) << 16) | ((g as u32) << 8) | (b as u32)
+}
+
+// Unpacks an ARGB u32 pixel into RGBA channels
+pub(crate) fn unpack(pixel: u32) -> [u8; 4] {
+    [
+        (pixel >> 16) as u8,
+        (pixel >> 8) as u8,
+        pixel as u8,
+        (pixel >> 24) as u8,
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Blends source onto an unpacked destination and unpacks result
+    fn blend(mode: BlendMode, destination: [u8; 4], source: [u8; 4]) -> [u8; 4] {
+        let [r, g, b, a] = destination;
+
+        unpack(mode.blend(pack(r, g, b, a), source))
+    }
+
+    #[test]
+    fn replace_copies_source_even_when_transparent() {
+        let destination = [10, 20, 30, 255];
+
+        assert_eq!(
+            blend(BlendMode::Replace, destination, [1, 2, 3, 0]),
+            [1, 2, 3, 0]
+        );
+        assert_eq!(
+            blend(BlendMode::Replace, destination, [1, 2, 3, 128]),
+            [1, 2, 3, 128]
+        );
+        assert_eq!(
+            blend(BlendMode::Replace, destination, [1, 2, 3, 255]),
+            [1, 2, 3, 255]
+        );
+    }
+
+    #[test]
+    fn alpha_composites_source_over_destination() {
+        let destination = [100, 200, 50, 255];
+        let source = |alpha| [200, 100, 250, alpha];
+
+        assert_eq!(blend(BlendMode::Alpha, destination, source(0)), destination);
+        assert_eq!(
+            blend(BlendMode::Alpha, destination, source(128)),
+            [150, 149, 150, 255]
+        );
+        assert_eq!(
+            blend(BlendMode::Alpha, destination, source(255)),
+            source(255)
+        );
+    }
+
+    #[test]
+    fn alpha_over_translucent_destination_weights_by_both_alphas() {
+        // Destination weight is 128 * 127 / 255, truncated to 63, giving 191 output alpha
+        assert_eq!(
+            blend(BlendMode::Alpha, [0, 0, 255, 128], [255, 0, 0, 128]),
+            [170, 0, 84, 191]
+        );
+
+        // Nothing underneath, so color is kept exactly instead of darkening towards black
+        assert_eq!(
+            blend(BlendMode::Alpha, [0, 0, 0, 0], [200, 100, 250, 128]),
+            [200, 100, 250, 128]
+        );
+        assert_eq!(
+            blend(BlendMode::Alpha, [0, 0, 0, 0], [200, 100, 250, 1]),
+            [200, 100, 250, 1]
+        );
+    }
+
+    #[test]
+    fn alpha_rounding_never_overshoots_or_loses_white() {
+        let white = [255, 255, 255, 255];
+
+        assert_eq!(blend(BlendMode::Alpha, white, [255, 255, 255, 128]), white);
+        assert_eq!(
+            blend(BlendMode::Alpha, white, [0, 0, 0, 1]),
+            [254, 254, 254, 255]
+        );
+        assert_eq!(
+            blend(BlendMode::Alpha, [0, 0, 0, 255], [255, 255, 255, 254]),
+            [254, 254, 254, 255]
+        );
+    }
+
+    #[test]
+    fn additive_adds_source_scaled_by_alpha() {
+        let destination = [10, 20, 30, 255];
+        let source = |alpha| [200, 100, 250, alpha];
+
+        assert_eq!(
+            blend(BlendMode::Additive, destination, source(0)),
+            destination
+        );
+        assert_eq!(
+            blend(BlendMode::Additive, destination, source(128)),
+            [110, 70, 155, 255]
+        );
+        assert_eq!(
+            blend(BlendMode::Additive, destination, source(255)),
+            [210, 120, 255, 255]
+        );
+        assert_eq!(
+            blend(BlendMode::Additive, [0, 0, 0, 0], source(128)),
+            [100, 50, 125, 128]
+        );
+    }
+
+    #[test]
+    fn multiply_fades_towards_no_change_with_alpha_and_keeps_destination_alpha() {
+        let destination = [200, 100, 50, 77];
+        let source = |alpha| [128, 255, 0, alpha];
+
+        assert_eq!(
+            blend(BlendMode::Multiply, destination, source(0)),
+            destination
+        );
+        assert_eq!(
+            blend(BlendMode::Multiply, destination, source(128)),
+            [149, 100, 24, 77]
+        );
+        assert_eq!(
+            blend(BlendMode::Multiply, destination, source(255)),
+            [100, 100, 0, 77]
+        );
+    }
+}
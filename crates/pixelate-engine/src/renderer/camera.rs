@@ -0,0 +1,315 @@
+use super::Rect;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Camera2D {
+    pub position: (f32, f32),
+    pub zoom: f32,
+    pub rotation: f32,
+    shake_intensity: f32,
+    shake_duration: f32,
+    shake_remaining: f32,
+    shake_offset: (f32, f32),
+    shake_seed: u32,
+}
+
+impl Camera2D {
+    // Creates a camera centered on given world position without zoom or rotation
+    pub fn new(position: (f32, f32)) -> Self {
+        Self {
+            position,
+            zoom: 1.0,
+            rotation: 0.0,
+            shake_intensity: 0.0,
+            shake_duration: 0.0,
+            shake_remaining: 0.0,
+            shake_offset: (0.0, 0.0),
+            shake_seed: 0x9E37_79B9,
+        }
+    }
+
+    // Starts shaking by up to intensity screen pixels, fading out over duration seconds
+    pub fn shake(&mut self, intensity: f32, duration: f32) {
+        // A weaker shake never cuts a stronger one short
+        if self.is_shaking() && intensity < self.current_shake_strength() {
+            return;
+        }
+
+        self.shake_intensity = intensity;
+        self.shake_duration = duration.max(f32::EPSILON);
+        self.shake_remaining = duration;
+    }
+
+    // Checks if a shake is still running
+    pub fn is_shaking(&self) -> bool {
+        self.shake_remaining > 0.0
+    }
+
+    // Returns current shake displacement in screen pixels
+    pub fn shake_offset(&self) -> (f32, f32) {
+        self.shake_offset
+    }
+
+    // Advances shake by elapsed seconds, picking a new random displacement
+    pub fn update(&mut self, delta_time: f32) {
+        self.shake_remaining = (self.shake_remaining - delta_time).max(0.0);
+
+        if !self.is_shaking() {
+            self.shake_offset = (0.0, 0.0);
+            return;
+        }
+
+        let strength = self.current_shake_strength();
+        let offset_x = (self.next_random() * 2.0 - 1.0) * strength;
+        let offset_y = (self.next_random() * 2.0 - 1.0) * strength;
+
+        self.shake_offset = (offset_x, offset_y);
+    }
+
+    // Converts a world position to a screen position within a viewport of given size
+    pub fn world_to_screen(&self, point: (f32, f32), viewport: (u32, u32)) -> (f32, f32) {
+        self.view(viewport).to_screen(point.0, point.1)
+    }
+
+    // Converts a screen position within a viewport of given size to a world position
+    pub fn screen_to_world(&self, point: (f32, f32), viewport: (u32, u32)) -> (f32, f32) {
+        self.view(viewport).to_world(point.0, point.1)
+    }
+
+    // Returns smallest world rectangle containing everything visible in viewport
+    pub fn visible_rect(&self, viewport: (u32, u32)) -> Rect {
+        let view = self.view(viewport);
+        let (width, height) = (viewport.0 as f32, viewport.1 as f32);
+
+        let (mut min_x, mut min_y) = (f32::MAX, f32::MAX);
+        let (mut max_x, mut max_y) = (f32::MIN, f32::MIN);
+
+        for (corner_x, corner_y) in [(0.0, 0.0), (width, 0.0), (0.0, height), (width, height)] {
+            let (world_x, world_y) = view.to_world(corner_x, corner_y);
+
+            min_x = min_x.min(world_x);
+            min_y = min_y.min(world_y);
+            max_x = max_x.max(world_x);
+            max_y = max_y.max(world_y);
+        }
+
+        let (left, top) = (min_x.floor() as i32, min_y.floor() as i32);
+
+        Rect::new(
+            left,
+            top,
+            (max_x.ceil() as i32 - left).max(0) as u32,
+            (max_y.ceil() as i32 - top).max(0) as u32,
+        )
+    }
+
+    // Returns transform from world to screen for a viewport of given size
+    pub(crate) fn view(&self, viewport: (u32, u32)) -> View {
+        let (sin, cos) = self.rotation.sin_cos();
+
+        View {
+            position: self.position,
+            center: (
+                viewport.0 as f32 / 2.0 + self.shake_offset.0,
+                viewport.1 as f32 / 2.0 + self.shake_offset.1,
+            ),
+            zoom: self.zoom,
+            rotation: -self.rotation,
+            sin,
+            cos,
+        }
+    }
+
+    // Returns shake amplitude, fading linearly to zero over its duration
+    fn current_shake_strength(&self) -> f32 {
+        self.shake_intensity * self.shake_remaining / self.shake_duration
+    }
+
+    // Returns a pseudo-random value in range 0 to 1 using xorshift
+    fn next_random(&mut self) -> f32 {
+        self.shake_seed ^= self.shake_seed << 13;
+        self.shake_seed ^= self.shake_seed >> 17;
+        self.shake_seed ^= self.shake_seed << 5;
+
+        (self.shake_seed >> 8) as f32 / (1 << 24) as f32
+    }
+}
+
+impl Default for Camera2D {
+    fn default() -> Self {
+        Self::new((0.0, 0.0))
+    }
+}
+
+// World to screen transform of a camera for one viewport size
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct View {
+    position: (f32, f32),
+    center: (f32, f32),
+    pub(crate) zoom: f32,
+    pub(crate) rotation: f32,
+    sin: f32,
+    cos: f32,
+}
+
+impl View {
+    // Maps a continuous world position to a continuous screen position
+    pub(crate) fn to_screen(self, x: f32, y: f32) -> (f32, f32) {
+        let (offset_x, offset_y) = (x - self.position.0, y - self.position.1);
+        let rotated_x = offset_x * self.cos + offset_y * self.sin;
+        let rotated_y = offset_y * self.cos - offset_x * self.sin;
+
+        (
+            self.center.0 + rotated_x * self.zoom,
+            self.center.1 + rotated_y * self.zoom,
+        )
+    }
+
+    // Maps a continuous screen position back to a continuous world position
+    pub(crate) fn to_world(self, x: f32, y: f32) -> (f32, f32) {
+        let offset_x = (x - self.center.0) / self.zoom;
+        let offset_y = (y - self.center.1) / self.zoom;
+
+        (
+            self.position.0 + offset_x * self.cos - offset_y * self.sin,
+            self.position.1 + offset_x * self.sin + offset_y * self.cos,
+        )
+    }
+
+    // Maps a continuous world position to raster space, where pixel centers are integers
+    pub(crate) fn to_raster(self, x: f32, y: f32) -> (f32, f32) {
+        let (screen_x, screen_y) = self.to_screen(x, y);
+
+        (screen_x - 0.5, screen_y - 0.5)
+    }
+
+    // Maps a world pixel center given in raster space to screen raster space
+    pub(crate) fn center_to_raster(self, x: f32, y: f32) -> (f32, f32) {
+        self.to_raster(x + 0.5, y + 0.5)
+    }
+
+    // Returns screen thickness of a one pixel wide world line
+    pub(crate) fn pixel_thickness(self) -> f32 {
+        self.zoom.max(1.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::FRAC_PI_2;
+
+    // Asserts two points match up to float rounding
+    fn assert_close(actual: (f32, f32), expected: (f32, f32)) {
+        assert!(
+            (actual.0 - expected.0).abs() < 1e-3 && (actual.1 - expected.1).abs() < 1e-3,
+            "{:?} != {:?}",
+            actual,
+            expected
+        );
+    }
+
+    #[test]
+    fn screen_and_world_round_trip_with_zoom_and_rotation() {
+        let mut camera = Camera2D::new((37.0, -12.5));
+        camera.zoom = 2.5;
+        camera.rotation = 0.7;
+        let view = camera.view((160, 90));
+
+        for point in [(37.0, -12.5), (0.0, 0.0), (100.25, 40.75), (-8.0, 3.5)] {
+            let (screen_x, screen_y) = view.to_screen(point.0, point.1);
+            assert_close(view.to_world(screen_x, screen_y), point);
+        }
+
+        for point in [(0.0, 0.0), (80.0, 45.0), (159.5, 12.25)] {
+            let (world_x, world_y) = view.to_world(point.0, point.1);
+            assert_close(view.to_screen(world_x, world_y), point);
+        }
+    }
+
+    #[test]
+    fn to_screen_applies_zoom_then_rotation_around_viewport_center() {
+        let mut camera = Camera2D::new((10.0, 20.0));
+        camera.zoom = 2.0;
+
+        let view = camera.view((100, 100));
+        assert_close(view.to_screen(10.0, 20.0), (50.0, 50.0));
+        assert_close(view.to_screen(11.0, 20.0), (52.0, 50.0));
+
+        // A quarter turn of camera makes world right point up on screen
+        camera.rotation = FRAC_PI_2;
+        let view = camera.view((100, 100));
+        assert_close(view.to_screen(11.0, 20.0), (50.0, 48.0));
+        assert_close(view.to_screen(10.0, 21.0), (52.0, 50.0));
+    }
+
+    #[test]
+    fn center_to_raster_maps_pixel_centers_and_round_trips() {
+        let camera = Camera2D::new((10.0, 20.0));
+        let view = camera.view((100, 100));
+
+        // Without zoom or rotation world pixels land exactly on screen pixels
+        assert_close(view.center_to_raster(10.0, 20.0), (50.0, 50.0));
+        assert_close(view.center_to_raster(-3.0, 7.0), (37.0, 37.0));
+
+        let mut camera = Camera2D::new((4.0, -6.0));
+        camera.zoom = 3.0;
+        camera.rotation = -1.1;
+        let view = camera.view((64, 48));
+
+        for pixel in [(4.0, -6.0), (0.0, 0.0), (12.0, 3.0)] {
+            let (raster_x, raster_y) = view.center_to_raster(pixel.0, pixel.1);
+            let (world_x, world_y) = view.to_world(raster_x + 0.5, raster_y + 0.5);
+
+            assert_close((world_x - 0.5, world_y - 0.5), pixel);
+        }
+    }
+
+    #[test]
+    fn pixel_thickness_never_drops_below_one_pixel() {
+        let mut camera = Camera2D::new((0.0, 0.0));
+
+        camera.zoom = 0.25;
+        assert_eq!(camera.view((10, 10)).pixel_thickness(), 1.0);
+
+        camera.zoom = 3.0;
+        assert_eq!(camera.view((10, 10)).pixel_thickness(), 3.0);
+    }
+
+    #[test]
+    fn shake_is_deterministic_and_decays_to_zero() {
+        let mut camera = Camera2D::default();
+        let mut replay = Camera2D::default();
+        camera.shake(4.0, 1.0);
+        replay.shake(4.0, 1.0);
+
+        for step in 1..=10 {
+            camera.update(0.1);
+            replay.update(0.1);
+
+            // Same seed gives same sequence, bounded by linearly fading strength
+            let (offset_x, offset_y) = camera.shake_offset();
+            let strength = 4.0 * (1.0 - step as f32 * 0.1);
+            assert_eq!(camera.shake_offset(), replay.shake_offset());
+            assert!(offset_x.abs() <= strength + 1e-4 && offset_y.abs() <= strength + 1e-4);
+        }
+
+        assert!(!camera.is_shaking());
+        assert_eq!(camera.shake_offset(), (0.0, 0.0));
+        assert_close(camera.view((10, 10)).to_screen(0.0, 0.0), (5.0, 5.0));
+    }
+
+    #[test]
+    fn weaker_shake_does_not_cut_stronger_one_short() {
+        let mut camera = Camera2D::default();
+        camera.shake(8.0, 1.0);
+        camera.update(0.5);
+
+        camera.shake(1.0, 0.1);
+        camera.update(0.2);
+        assert!(camera.is_shaking());
+
+        camera.shake(8.0, 0.1);
+        camera.update(0.2);
+        assert!(!camera.is_shaking());
+    }
+}
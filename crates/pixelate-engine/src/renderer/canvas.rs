@@ -0,0 +1,158 @@
+use super::blend;
+use crate::assets::ImageData;
+
+#[derive(Clone)]
+pub struct Canvas {
+    pub(crate) buffer: Vec<u32>,
+    pub(crate) width: u32,
+    pub(crate) height: u32,
+}
+
+impl Canvas {
+    // Creates a fully transparent canvas of given size
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            buffer: vec![0; (width * height) as usize],
+            width,
+            height,
+        }
+    }
+
+    // Creates a canvas holding a copy of image pixels
+    pub fn from_image(image: &ImageData) -> Self {
+        let buffer = image
+            .rgba_bytes
+            .chunks_exact(4)
+            .map(|rgba| blend::pack(rgba[0], rgba[1], rgba[2], rgba[3]))
+            .collect();
+
+        Self {
+            buffer,
+            width: image.width,
+            height: image.height,
+        }
+    }
+
+    // Returns width of canvas in pixels
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    // Returns height of canvas in pixels
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    // Returns canvas as packed ARGB pixels in row-major order
+    pub fn pixels(&self) -> &[u32] {
+        &self.buffer
+    }
+
+    // Returns RGBA color of pixel at specified coordinates
+    pub fn pixel(&self, x: i32, y: i32) -> Option<[u8; 4]> {
+        if x < 0 || y < 0 || x >= self.width as i32 || y >= self.height as i32 {
+            return None;
+        }
+
+        let buffer_index = (y as u32 * self.width + x as u32) as usize;
+
+        Some(blend::unpack(self.buffer[buffer_index]))
+    }
+
+    // Resizes canvas, discarding its contents
+    pub fn resize(&mut self, width: u32, height: u32) {
+        self.width = width;
+        self.height = height;
+        self.buffer.clear();
+        self.buffer.resize((width * height) as usize, 0);
+    }
+
+    // Copies canvas pixels into a new image, keeping transparency
+    pub fn to_image(&self) -> ImageData {
+        let rgba_bytes = self
+            .buffer
+            .iter()
+            .flat_map(|&pixel| blend::unpack(pixel))
+            .collect();
+
+        ImageData::from_bytes(rgba_bytes, self.width, self.height)
+    }
+}
+
+// Anything image drawing can sample pixels from
+pub(crate) trait PixelSource: Sync {
+    fn size(&self) -> (u32, u32);
+
+    fn sample(&self, x: i32, y: i32) -> Option<[u8; 4]>;
+
+    // Returns a whole row when pixels are stored contiguously, letting blits skip per-pixel lookups
+    fn row(&self, _y: i32) -> Option<PixelRow<'_>> {
+        None
+    }
+}
+
+// Contiguous row of source pixels in either storage format
+pub(crate) enum PixelRow<'a> {
+    Rgba(&'a [u8]),
+    Packed(&'a [u32]),
+}
+
+impl PixelRow<'_> {
+    // Returns RGBA color at given column, or None outside row
+    pub(crate) fn get(&self, x: i32) -> Option<[u8; 4]> {
+        let x = usize::try_from(x).ok()?;
+
+        match self {
+            PixelRow::Rgba(bytes) => {
+                let rgba = bytes.get(x * 4..x * 4 + 4)?;
+                Some([rgba[0], rgba[1], rgba[2], rgba[3]])
+            }
+            PixelRow::Packed(pixels) => pixels.get(x).map(|&pixel| blend::unpack(pixel)),
+        }
+    }
+}
+
+impl PixelSource for ImageData {
+    fn size(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+
+    fn sample(&self, x: i32, y: i32) -> Option<[u8; 4]> {
+        self.pixel(x, y)
+    }
+
+    fn row(&self, y: i32) -> Option<PixelRow<'_>> {
+        if y < 0 || y >= self.height as i32 {
+            return None;
+        }
+
+        let row_length = self.width as usize * 4;
+        let row_start = y as usize * row_length;
+
+        self.rgba_bytes
+            .get(row_start..row_start + row_length)
+            .map(PixelRow::Rgba)
+    }
+}
+
+impl PixelSource for Canvas {
+    fn size(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+
+    fn sample(&self, x: i32, y: i32) -> Option<[u8; 4]> {
+        self.pixel(x, y)
+    }
+
+    fn row(&self, y: i32) -> Option<PixelRow<'_>> {
+        if y < 0 || y >= self.height as i32 {
+            return None;
+        }
+
+        let row_start = (y as u32 * self.width) as usize;
+
+        Some(PixelRow::Packed(
+            &self.buffer[row_start..row_start + self.width as usize],
+        ))
+    }
+}
@@ -0,0 +1,162 @@
+use super::{LineStyle, Renderer};
+use std::f32::consts::TAU;
+
+// Target length in pixels of each straight piece when flattening curves
+const FLATTEN_STEP: f32 = 2.0;
+const MAX_CURVE_SEGMENTS: usize = 1024;
+
+impl Renderer {
+    // Draws a quadratic Bezier curve from start to end bent toward control
+    pub fn draw_quadratic_bezier(
+        &mut self,
+        start: (f32, f32),
+        control: (f32, f32),
+        end: (f32, f32),
+        style: &LineStyle,
+        color: [u8; 4],
+    ) {
+        let segments = segment_count(distance(start, control) + distance(control, end));
+
+        let points: Vec<(f32, f32)> = (0..=segments)
+            .map(|step| {
+                let t = step as f32 / segments as f32;
+                let u = 1.0 - t;
+                let (a, b, c) = (u * u, 2.0 * u * t, t * t);
+
+                (
+                    a * start.0 + b * control.0 + c * end.0,
+                    a * start.1 + b * control.1 + c * end.1,
+                )
+            })
+            .collect();
+
+        self.stroke_path(&points, false, style, color);
+    }
+
+    // Draws a cubic Bezier curve from start to end shaped by two control points
+    pub fn draw_cubic_bezier(
+        &mut self,
+        start: (f32, f32),
+        first_control: (f32, f32),
+        second_control: (f32, f32),
+        end: (f32, f32),
+        style: &LineStyle,
+        color: [u8; 4],
+    ) {
+        let segments = segment_count(
+            distance(start, first_control)
+                + distance(first_control, second_control)
+                + distance(second_control, end),
+        );
+
+        let points: Vec<(f32, f32)> = (0..=segments)
+            .map(|step| {
+                let t = step as f32 / segments as f32;
+                let u = 1.0 - t;
+                let (a, b, c, d) = (u * u * u, 3.0 * u * u * t, 3.0 * u * t * t, t * t * t);
+
+                (
+                    a * start.0 + b * first_control.0 + c * second_control.0 + d * end.0,
+                    a * start.1 + b * first_control.1 + c * second_control.1 + d * end.1,
+                )
+            })
+            .collect();
+
+        self.stroke_path(&points, false, style, color);
+    }
+
+    // Draws a circular arc; angles are radians from +x, increasing clockwise on screen
+    pub fn draw_arc(
+        &mut self,
+        center: (f32, f32),
+        radius: f32,
+        start_angle: f32,
+        end_angle: f32,
+        style: &LineStyle,
+        color: [u8; 4],
+    ) {
+        let (points, full_turn) = arc_points(center, radius, start_angle, end_angle);
+
+        if full_turn {
+            self.stroke_path(&points[..points.len() - 1], true, style, color);
+        } else {
+            self.stroke_path(&points, false, style, color);
+        }
+    }
+
+    // Draws outline of a pie slice, joining arc ends to center
+    pub fn draw_pie(
+        &mut self,
+        center: (f32, f32),
+        radius: f32,
+        start_angle: f32,
+        end_angle: f32,
+        style: &LineStyle,
+        color: [u8; 4],
+    ) {
+        let (mut points, full_turn) = arc_points(center, radius, start_angle, end_angle);
+
+        if full_turn {
+            points.pop();
+        } else {
+            points.push(center);
+        }
+
+        self.stroke_path(&points, true, style, color);
+    }
+
+    // Draws a filled pie slice between two angles
+    pub fn fill_pie(
+        &mut self,
+        center: (f32, f32),
+        radius: f32,
+        start_angle: f32,
+        end_angle: f32,
+        color: [u8; 4],
+    ) {
+        let (mut points, full_turn) = arc_points(center, radius, start_angle, end_angle);
+
+        if full_turn {
+            points.pop();
+        } else {
+            points.push(center);
+        }
+
+        // Arc points address pixel centers, polygon fill expects pixel edges
+        let points: Vec<(f32, f32)> = points.iter().map(|&(x, y)| (x + 0.5, y + 0.5)).collect();
+
+        self.fill_polygon_points(&points, color);
+    }
+}
+
+// Returns points along an arc and whether it sweeps a full circle
+fn arc_points(
+    center: (f32, f32),
+    radius: f32,
+    start_angle: f32,
+    end_angle: f32,
+) -> (Vec<(f32, f32)>, bool) {
+    let sweep = (end_angle - start_angle).clamp(-TAU, TAU);
+    let full_turn = sweep.abs() >= TAU;
+    let segments = segment_count(sweep.abs() * radius.abs());
+
+    let points = (0..=segments)
+        .map(|step| {
+            let angle = start_angle + sweep * step as f32 / segments as f32;
+            let (sin, cos) = angle.sin_cos();
+
+            (center.0 + radius * cos, center.1 + radius * sin)
+        })
+        .collect();
+
+    (points, full_turn)
+}
+
+// Returns number of straight pieces needed to approximate a curve of given length
+fn segment_count(length: f32) -> usize {
+    ((length / FLATTEN_STEP).ceil() as usize).clamp(4, MAX_CURVE_SEGMENTS)
+}
+
+fn distance(from: (f32, f32), to: (f32, f32)) -> f32 {
+    ((to.0 - from.0).powi(2) + (to.1 - from.1).powi(2)).sqrt()
+}
@@ -0,0 +1,200 @@
+use super::Rect;
+use std::collections::VecDeque;
+
+// Separate regions kept per frame before they are merged into their bounds
+const MAX_REGIONS: usize = 16;
+
+// Past frames remembered to bring older back buffers up to date
+const HISTORY_LENGTH: usize = 3;
+
+// Changed areas of screen buffer, where None stands for whole buffer
+pub(crate) struct DamageTracker {
+    current: Option<Vec<Rect>>,
+    history: VecDeque<Option<Vec<Rect>>>,
+}
+
+impl DamageTracker {
+    // Creates a tracker treating whole buffer as changed, as nothing was presented yet
+    pub(crate) fn new() -> Self {
+        Self {
+            current: None,
+            history: VecDeque::with_capacity(HISTORY_LENGTH),
+        }
+    }
+
+    // Records an area changed in current frame
+    pub(crate) fn add(&mut self, rect: Rect) {
+        let Some(regions) = self.current.as_mut() else {
+            return;
+        };
+
+        if rect.is_empty() || regions.iter().any(|region| covers(region, &rect)) {
+            return;
+        }
+
+        // Regions touching new area grow to include it instead of adding another
+        if let Some(region) = regions.iter_mut().find(|region| touches(region, &rect)) {
+            *region = region.union(&rect);
+            return;
+        }
+
+        regions.push(rect);
+
+        if regions.len() > MAX_REGIONS {
+            let bounds = regions
+                .iter()
+                .fold(Rect::default(), |bounds, region| bounds.union(region));
+            *regions = vec![bounds];
+        }
+    }
+
+    // Records whole buffer as changed in current frame
+    pub(crate) fn add_all(&mut self) {
+        self.current = None;
+    }
+
+    // Returns areas changed in current frame, or None when everything changed
+    pub(crate) fn current(&self) -> Option<&[Rect]> {
+        self.current.as_deref()
+    }
+
+    // Returns areas changed since a back buffer was presented given frames ago, or None for all
+    pub(crate) fn since(&self, age: u8) -> Option<Vec<Rect>> {
+        let older_frames = (age as usize).checked_sub(1)?;
+
+        if older_frames > self.history.len() {
+            return None;
+        }
+
+        let mut regions = self.current.clone()?;
+
+        for frame in self.history.iter().take(older_frames) {
+            regions.extend(frame.as_deref()?);
+        }
+
+        Some(regions)
+    }
+
+    // Moves current frame into history and starts a frame with nothing changed
+    pub(crate) fn finish_frame(&mut self) {
+        let finished = self.current.replace(Vec::new());
+
+        if self.history.len() == HISTORY_LENGTH {
+            self.history.pop_back();
+        }
+
+        self.history.push_front(finished);
+    }
+}
+
+// Checks if outer rectangle fully contains inner one
+fn covers(outer: &Rect, inner: &Rect) -> bool {
+    inner.x >= outer.x
+        && inner.y >= outer.y
+        && inner.right() <= outer.right()
+        && inner.bottom() <= outer.bottom()
+}
+
+// Checks if rectangles overlap or share an edge
+fn touches(first: &Rect, second: &Rect) -> bool {
+    first.x <= second.right()
+        && second.x <= first.right()
+        && first.y <= second.bottom()
+        && second.y <= first.bottom()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Creates a tracker past its first full frame, so regions are tracked
+    fn tracking() -> DamageTracker {
+        let mut tracker = DamageTracker::new();
+        tracker.finish_frame();
+        tracker
+    }
+
+    #[test]
+    fn touching_regions_merge_and_covered_ones_are_dropped() {
+        let mut tracker = tracking();
+
+        tracker.add(Rect::new(0, 0, 4, 4));
+        tracker.add(Rect::new(4, 0, 4, 4));
+        tracker.add(Rect::new(1, 1, 2, 2));
+        tracker.add(Rect::new(0, 0, 0, 0));
+        tracker.add(Rect::new(20, 20, 2, 2));
+
+        assert_eq!(
+            tracker.current(),
+            Some(&[Rect::new(0, 0, 8, 4), Rect::new(20, 20, 2, 2)][..])
+        );
+    }
+
+    #[test]
+    fn too_many_regions_collapse_into_their_bounds() {
+        let mut tracker = tracking();
+
+        for index in 0..=MAX_REGIONS as i32 {
+            tracker.add(Rect::new(index * 4, index * 4, 1, 1));
+        }
+
+        let last = MAX_REGIONS as i32 * 4;
+        assert_eq!(
+            tracker.current(),
+            Some(&[Rect::new(0, 0, last as u32 + 1, last as u32 + 1)][..])
+        );
+    }
+
+    #[test]
+    fn since_combines_frames_back_to_buffer_age() {
+        let mut tracker = tracking();
+        tracker.add(Rect::new(0, 0, 1, 1));
+        tracker.finish_frame();
+        tracker.add(Rect::new(10, 10, 1, 1));
+        tracker.finish_frame();
+        tracker.add(Rect::new(20, 20, 1, 1));
+
+        // Age 0 means buffer contents are unknown
+        assert_eq!(tracker.since(0), None);
+        assert_eq!(tracker.since(1), Some(vec![Rect::new(20, 20, 1, 1)]));
+        assert_eq!(
+            tracker.since(3),
+            Some(vec![
+                Rect::new(20, 20, 1, 1),
+                Rect::new(10, 10, 1, 1),
+                Rect::new(0, 0, 1, 1),
+            ])
+        );
+
+        // Fourth frame back is the first one, which changed everything
+        assert_eq!(tracker.since(4), None);
+    }
+
+    #[test]
+    fn since_falls_back_to_full_frame_beyond_history() {
+        let mut tracker = tracking();
+
+        for _ in 0..HISTORY_LENGTH + 2 {
+            tracker.add(Rect::new(0, 0, 1, 1));
+            tracker.finish_frame();
+        }
+
+        assert!(tracker.since(HISTORY_LENGTH as u8 + 1).is_some());
+        assert_eq!(tracker.since(HISTORY_LENGTH as u8 + 2), None);
+        assert_eq!(tracker.since(u8::MAX), None);
+    }
+
+    #[test]
+    fn whole_buffer_damage_covers_later_regions() {
+        let mut tracker = tracking();
+        tracker.add_all();
+        tracker.add(Rect::new(0, 0, 1, 1));
+
+        assert_eq!(tracker.current(), None);
+        assert_eq!(tracker.since(1), None);
+
+        tracker.finish_frame();
+        assert_eq!(tracker.since(1), Some(Vec::new()));
+        assert_eq!(tracker.since(2), None);
+    }
+}
@@ -19,6 +19,14 @@ pub enum RendererError {
     PresentFailed {
         source: softbuffer::SoftBufferError,
     },
+    RecordingStartFailed {
+        path: String,
+        source: std::io::Error,
+    },
+    RecordingFailed {
+        path: String,
+        source: image::ImageError,
+    },
 }
 
 impl Display for RendererError {
@@ -44,6 +52,12 @@ impl Display for RendererError {
             RendererError::PresentFailed { source } => {
                 write!(formatter, "Failed to present rendered frame: {}", source)
             }
+            RendererError::RecordingStartFailed { path, source } => {
+                write!(formatter, "Failed to start recording {}: {}", path, source)
+            }
+            RendererError::RecordingFailed { path, source } => {
+                write!(formatter, "Failed to write recording {}: {}", path, source)
+            }
         }
     }
 }
@@ -55,6 +69,8 @@ impl Error for RendererError {
             RendererError::SurfaceCreationFailed { source } => Some(source),
             RendererError::ResizeFailed { source, .. } => Some(source),
             RendererError::PresentFailed { source } => Some(source),
+            RendererError::RecordingStartFailed { source, .. } => Some(source),
+            RendererError::RecordingFailed { source, .. } => Some(source),
         }
     }
 }
@@ -0,0 +1,236 @@
+use super::{canvas::PixelSource, DrawParams, Rect, Renderer};
+use crate::assets::{ImageData, IndexedImage, Palette};
+
+#[derive(Clone)]
+pub struct IndexedCanvas {
+    pub(crate) indices: Vec<u8>,
+    pub(crate) width: u32,
+    pub(crate) height: u32,
+    pub(crate) palette: Palette,
+    pub(crate) transparent_index: Option<u8>,
+}
+
+impl IndexedCanvas {
+    // Creates an 8-bit canvas of given size filled with index zero
+    pub fn new(width: u32, height: u32, palette: Palette) -> Self {
+        Self {
+            indices: vec![0; (width * height) as usize],
+            width,
+            height,
+            palette,
+            transparent_index: None,
+        }
+    }
+
+    // Returns width of canvas in pixels
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    // Returns height of canvas in pixels
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    // Returns palette indices in row-major order
+    pub fn indices(&self) -> &[u8] {
+        &self.indices
+    }
+
+    // Returns palette colors are resolved through
+    pub fn palette(&self) -> &Palette {
+        &self.palette
+    }
+
+    // Returns palette for in-place edits such as color cycling
+    pub fn palette_mut(&mut self) -> &mut Palette {
+        &mut self.palette
+    }
+
+    // Replaces whole palette, recoloring everything drawn so far
+    pub fn set_palette(&mut self, palette: Palette) {
+        self.palette = palette;
+    }
+
+    // Returns index left out when canvas is drawn onto a 32-bit target
+    pub fn transparent_index(&self) -> Option<u8> {
+        self.transparent_index
+    }
+
+    // Sets index left out when canvas is drawn onto a 32-bit target
+    pub fn set_transparent_index(&mut self, transparent_index: Option<u8>) {
+        self.transparent_index = transparent_index;
+    }
+
+    // Returns palette index of pixel at specified coordinates
+    pub fn index(&self, x: i32, y: i32) -> Option<u8> {
+        if x < 0 || y < 0 || x >= self.width as i32 || y >= self.height as i32 {
+            return None;
+        }
+
+        Some(self.indices[(y as u32 * self.width + x as u32) as usize])
+    }
+
+    // Returns RGBA color of pixel at specified coordinates through palette
+    pub fn pixel(&self, x: i32, y: i32) -> Option<[u8; 4]> {
+        let index = self.index(x, y)?;
+
+        if Some(index) == self.transparent_index {
+            return Some([0, 0, 0, 0]);
+        }
+
+        Some(self.palette.color(index))
+    }
+
+    // Fills entire canvas with a palette index
+    pub fn clear(&mut self, index: u8) {
+        self.indices.fill(index);
+    }
+
+    // Sets a single pixel to a palette index
+    pub fn set_pixel(&mut self, x: i32, y: i32, index: u8) {
+        if x < 0 || y < 0 || x >= self.width as i32 || y >= self.height as i32 {
+            return;
+        }
+
+        self.indices[(y as u32 * self.width + x as u32) as usize] = index;
+    }
+
+    // Fills a rectangle with a palette index
+    pub fn fill_rectangle(&mut self, rect: Rect, index: u8) {
+        let Some(area) = rect.intersection(&Rect::new(0, 0, self.width, self.height)) else {
+            return;
+        };
+
+        for y in area.y..area.bottom() {
+            let row = (y as u32 * self.width) as usize;
+            self.indices[row + area.x as usize..row + area.right() as usize].fill(index);
+        }
+    }
+
+    // Draws a one pixel wide line between two points using Bresenham's algorithm
+    pub fn draw_line(&mut self, x1: i32, y1: i32, x2: i32, y2: i32, index: u8) {
+        let (delta_x, delta_y) = ((x2 - x1).abs(), -(y2 - y1).abs());
+        let (step_x, step_y) = ((x2 - x1).signum(), (y2 - y1).signum());
+        let (mut x, mut y) = (x1, y1);
+        let mut error = delta_x + delta_y;
+
+        loop {
+            self.set_pixel(x, y, index);
+
+            if x == x2 && y == y2 {
+                break;
+            }
+
+            let doubled_error = 2 * error;
+
+            if doubled_error >= delta_y {
+                error += delta_y;
+                x += step_x;
+            }
+
+            if doubled_error <= delta_x {
+                error += delta_x;
+                y += step_y;
+            }
+        }
+    }
+
+    // Copies an indexed image at given position, skipping its transparent index
+    pub fn draw_image(&mut self, image: &IndexedImage, x: i32, y: i32) {
+        self.blit_image(image, x, y, |index| index);
+    }
+
+    // Copies an indexed image through a remap table, e.g. to recolor a sprite per team
+    pub fn draw_image_remapped(&mut self, image: &IndexedImage, x: i32, y: i32, remap: &[u8; 256]) {
+        self.blit_image(image, x, y, |index| remap[index as usize]);
+    }
+
+    // Resolves every pixel through palette into a new RGBA image
+    pub fn to_image(&self) -> ImageData {
+        let rgba_bytes = (0..self.height as i32)
+            .flat_map(|y| (0..self.width as i32).map(move |x| (x, y)))
+            .flat_map(|(x, y)| self.pixel(x, y).unwrap_or([0, 0, 0, 0]))
+            .collect();
+
+        ImageData::from_bytes(rgba_bytes, self.width, self.height)
+    }
+
+    fn blit_image(&mut self, image: &IndexedImage, x: i32, y: i32, remap: impl Fn(u8) -> u8) {
+        for source_y in 0..image.height as i32 {
+            for source_x in 0..image.width as i32 {
+                let Some(index) = image.index(source_x, source_y) else {
+                    continue;
+                };
+
+                if Some(index) != image.transparent_index {
+                    self.set_pixel(x + source_x, y + source_y, remap(index));
+                }
+            }
+        }
+    }
+}
+
+impl PixelSource for IndexedCanvas {
+    fn size(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+
+    fn sample(&self, x: i32, y: i32) -> Option<[u8; 4]> {
+        self.pixel(x, y)
+    }
+}
+
+// Indexed image paired with palette it is resolved through
+struct PalettedImage<'a> {
+    image: &'a IndexedImage,
+    palette: &'a Palette,
+}
+
+impl PixelSource for PalettedImage<'_> {
+    fn size(&self) -> (u32, u32) {
+        (self.image.width, self.image.height)
+    }
+
+    fn sample(&self, x: i32, y: i32) -> Option<[u8; 4]> {
+        self.image.pixel(x, y, self.palette)
+    }
+}
+
+impl Renderer {
+    // Draws an indexed canvas resolved through its current palette at given position
+    pub fn draw_indexed_canvas(&mut self, canvas: &IndexedCanvas, x: i32, y: i32) {
+        let source = Rect::new(0, 0, canvas.width, canvas.height);
+
+        self.draw_region_of(canvas, source, Rect::new(x, y, canvas.width, canvas.height));
+    }
+
+    // Draws a source rectangle of an indexed canvas scaled into a destination rectangle
+    pub fn draw_indexed_canvas_region(
+        &mut self,
+        canvas: &IndexedCanvas,
+        source: Rect,
+        destination: Rect,
+    ) {
+        self.draw_region_of(canvas, source, destination);
+    }
+
+    // Draws an indexed canvas with flipping, rotation and scaling around a pivot
+    pub fn draw_indexed_canvas_ex(
+        &mut self,
+        canvas: &IndexedCanvas,
+        x: f32,
+        y: f32,
+        params: &DrawParams,
+    ) {
+        self.draw_transformed_of(canvas, x, y, params);
+    }
+
+    // Draws an indexed image resolved through given palette at given position
+    pub fn draw_indexed_image(&mut self, image: &IndexedImage, palette: &Palette, x: i32, y: i32) {
+        let source = Rect::new(0, 0, image.width, image.height);
+        let destination = Rect::new(x, y, image.width, image.height);
+
+        self.draw_region_of(&PalettedImage { image, palette }, source, destination);
+    }
+}
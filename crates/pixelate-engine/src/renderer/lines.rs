@@ -0,0 +1,657 @@
+use super::{Rect, Renderer};
+use std::f32::consts::SQRT_2;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LineCap {
+    // Ends exactly at endpoints
+    #[default]
+    Butt,
+    // Extends past endpoints by half thickness
+    Square,
+    // Ends in a half circle around endpoints
+    Round,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LineStyle {
+    pub(crate) thickness: f32,
+    pub(crate) cap: LineCap,
+    pub(crate) anti_aliased: bool,
+}
+
+impl LineStyle {
+    // Creates a style for one pixel wide, aliased lines with butt caps
+    pub fn new() -> Self {
+        Self {
+            thickness: 1.0,
+            cap: LineCap::Butt,
+            anti_aliased: false,
+        }
+    }
+
+    // Sets line thickness in pixels
+    pub fn thickness(mut self, thickness: f32) -> Self {
+        self.thickness = thickness.max(0.0);
+        self
+    }
+
+    // Sets shape of open line ends
+    pub fn cap(mut self, cap: LineCap) -> Self {
+        self.cap = cap;
+        self
+    }
+
+    // Enables smoothing edges through partial coverage
+    pub fn anti_aliased(mut self, anti_aliased: bool) -> Self {
+        self.anti_aliased = anti_aliased;
+        self
+    }
+}
+
+impl Default for LineStyle {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Renderer {
+    // Draws a line with given style; coordinates address pixel centers like draw_line
+    pub fn draw_line_styled(
+        &mut self,
+        x1: f32,
+        y1: f32,
+        x2: f32,
+        y2: f32,
+        style: &LineStyle,
+        color: [u8; 4],
+    ) {
+        self.stroke_path(&[(x1, y1), (x2, y2)], false, style, color);
+    }
+
+    // Draws connected line segments through points, with round joins when thick
+    pub fn draw_polyline(&mut self, points: &[(f32, f32)], style: &LineStyle, color: [u8; 4]) {
+        self.stroke_path(points, false, style, color);
+    }
+
+    // Draws a closed polygon outline through points
+    pub fn draw_polygon(&mut self, points: &[(f32, f32)], style: &LineStyle, color: [u8; 4]) {
+        self.stroke_path(points, true, style, color);
+    }
+
+    // Strokes a path of pixel centers, following active camera
+    pub(super) fn stroke_path(
+        &mut self,
+        points: &[(f32, f32)],
+        closed: bool,
+        style: &LineStyle,
+        color: [u8; 4],
+    ) {
+        let Some(view) = self.view() else {
+            self.stroke_screen_path(points, closed, style, color);
+            return;
+        };
+
+        let points: Vec<(f32, f32)> = points
+            .iter()
+            .map(|&(x, y)| view.center_to_raster(x, y))
+            .collect();
+        let style = style.thickness(style.thickness * view.zoom);
+
+        self.stroke_screen_path(&points, closed, &style, color);
+    }
+
+    // Rasterizes a path into a coverage mask, then blends it so overlaps are drawn once
+    pub(super) fn stroke_screen_path(
+        &mut self,
+        points: &[(f32, f32)],
+        closed: bool,
+        style: &LineStyle,
+        color: [u8; 4],
+    ) {
+        if points.is_empty() || style.thickness == 0.0 {
+            return;
+        }
+
+        let half_thickness = style.thickness / 2.0;
+        let clip = self.clip_bounds();
+
+        // Square cap corners stick out diagonally, up to half thickness times root two
+        let reach = match style.cap {
+            LineCap::Square => half_thickness * SQRT_2,
+            _ => half_thickness,
+        };
+
+        let Some(mut mask) = CoverageMask::around(points, reach + 1.0, &clip) else {
+            return;
+        };
+
+        let segment_count = match (closed, points.len()) {
+            (_, 1) => 1,
+            (true, 2) => 1,
+            (true, count) => count,
+            (false, count) => count - 1,
+        };
+
+        for index in 0..segment_count {
+            let start = points[index];
+            let end = points[(index + 1) % points.len()];
+
+            if style.thickness <= 1.0 {
+                // Far off-screen endpoints are pulled in so stepping never wanders far from mask
+                let Some((start, end)) = clip_segment(start, end, &mask.bounds, CLIP_MARGIN) else {
+                    continue;
+                };
+
+                if style.anti_aliased {
+                    mask.add_wu_line(start, end, style.thickness);
+                } else {
+                    mask.add_bresenham_line(start, end);
+                }
+
+                continue;
+            }
+
+            // Interior joints get round joins below, so only path ends use the cap style
+            let start_cap = if closed || index > 0 {
+                LineCap::Butt
+            } else {
+                style.cap
+            };
+            let end_cap = if closed || index + 1 < segment_count {
+                LineCap::Butt
+            } else {
+                style.cap
+            };
+
+            mask.add_thick_segment(
+                start,
+                end,
+                half_thickness,
+                start_cap,
+                end_cap,
+                style.anti_aliased,
+            );
+        }
+
+        if style.thickness > 1.0 && points.len() > 2 {
+            let joints = if closed {
+                points
+            } else {
+                &points[1..points.len() - 1]
+            };
+
+            for &joint in joints {
+                mask.add_disk(joint, half_thickness, style.anti_aliased);
+            }
+        }
+
+        self.fill_coverage(&mask, color);
+    }
+
+    // Blends a color through coverage mask, scaling alpha by coverage
+    fn fill_coverage(&mut self, mask: &CoverageMask, color: [u8; 4]) {
+        let [r, g, b, a] = color;
+
+        for (index, &coverage) in mask.coverage.iter().enumerate() {
+            if coverage == 0 {
+                continue;
+            }
+
+            let x = mask.bounds.x + (index as u32 % mask.bounds.width) as i32;
+            let y = mask.bounds.y + (index as u32 / mask.bounds.width) as i32;
+            let alpha = (a as u32 * coverage as u32 / 255) as u8;
+
+            self.plot(x, y, [r, g, b, alpha]);
+        }
+    }
+}
+
+// Distance outside a mask beyond which segments are cut; nearer ones rasterize unchanged
+const CLIP_MARGIN: f32 = 1024.0;
+
+// Per-pixel coverage over a clipped region, combined by maximum
+struct CoverageMask {
+    bounds: Rect,
+    coverage: Vec<u8>,
+}
+
+impl CoverageMask {
+    // Creates a mask covering points expanded by margin, clipped to given bounds
+    fn around(points: &[(f32, f32)], margin: f32, clip: &Rect) -> Option<Self> {
+        let (mut min_x, mut min_y) = (f32::MAX, f32::MAX);
+        let (mut max_x, mut max_y) = (f32::MIN, f32::MIN);
+
+        for &(x, y) in points {
+            min_x = min_x.min(x);
+            min_y = min_y.min(y);
+            max_x = max_x.max(x);
+            max_y = max_y.max(y);
+        }
+
+        // Clamped while still floats, since far off-screen points would overflow integer sizes
+        let left = (min_x - margin).floor().max(clip.x as f32);
+        let top = (min_y - margin).floor().max(clip.y as f32);
+        let right = ((max_x + margin).ceil() + 1.0).min(clip.right() as f32);
+        let bottom = ((max_y + margin).ceil() + 1.0).min(clip.bottom() as f32);
+
+        if !(left < right && top < bottom) {
+            return None;
+        }
+
+        let bounds = Rect::new(
+            left as i32,
+            top as i32,
+            (right - left) as u32,
+            (bottom - top) as u32,
+        );
+
+        Some(Self {
+            bounds,
+            coverage: vec![0; (bounds.width * bounds.height) as usize],
+        })
+    }
+
+    // Raises coverage of a pixel to given value in range 0 to 1
+    fn add(&mut self, x: i32, y: i32, value: f32) {
+        if !self.bounds.contains(x, y) || value <= 0.0 {
+            return;
+        }
+
+        let index =
+            ((y - self.bounds.y) as u32 * self.bounds.width + (x - self.bounds.x) as u32) as usize;
+        let value = (value.min(1.0) * 255.0).round() as u8;
+
+        self.coverage[index] = self.coverage[index].max(value);
+    }
+
+    // Adds a fully covered one pixel line between rounded endpoints
+    fn add_bresenham_line(&mut self, start: (f32, f32), end: (f32, f32)) {
+        let (mut current_x, mut current_y) = (start.0.round() as i32, start.1.round() as i32);
+        let (end_x, end_y) = (end.0.round() as i32, end.1.round() as i32);
+
+        let delta_x = (end_x - current_x).abs();
+        let delta_y = -(end_y - current_y).abs();
+        let step_x = if current_x < end_x { 1 } else { -1 };
+        let step_y = if current_y < end_y { 1 } else { -1 };
+        let mut error = delta_x + delta_y;
+
+        loop {
+            self.add(current_x, current_y, 1.0);
+
+            if current_x == end_x && current_y == end_y {
+                break;
+            }
+
+            let error_doubled = 2 * error;
+            if error_doubled >= delta_y {
+                error += delta_y;
+                current_x += step_x;
+            }
+
+            if error_doubled <= delta_x {
+                error += delta_x;
+                current_y += step_y;
+            }
+        }
+    }
+
+    // Adds an anti-aliased one pixel line using Xiaolin Wu's algorithm
+    fn add_wu_line(&mut self, start: (f32, f32), end: (f32, f32), intensity: f32) {
+        let (mut x0, mut y0) = start;
+        let (mut x1, mut y1) = end;
+
+        let steep = (y1 - y0).abs() > (x1 - x0).abs();
+        if steep {
+            (x0, y0, x1, y1) = (y0, x0, y1, x1);
+        }
+
+        if x0 > x1 {
+            (x0, y0, x1, y1) = (x1, y1, x0, y0);
+        }
+
+        let delta_x = x1 - x0;
+        let gradient = if delta_x == 0.0 {
+            1.0
+        } else {
+            (y1 - y0) / delta_x
+        };
+
+        let mut plot = |major: i32, minor: i32, value: f32| {
+            if steep {
+                self.add(minor, major, value * intensity);
+            } else {
+                self.add(major, minor, value * intensity);
+            }
+        };
+
+        // Endpoints are drawn at full strength so polyline joints do not dim
+        let start_major = x0.round() as i32;
+        let end_major = x1.round() as i32;
+        let mut intersection = y0 + gradient * (start_major as f32 - x0);
+
+        for major in start_major..=end_major {
+            let minor = intersection.floor();
+            let fraction = intersection - minor;
+
+            plot(major, minor as i32, 1.0 - fraction);
+            plot(major, minor as i32 + 1, fraction);
+
+            intersection += gradient;
+        }
+    }
+
+    // Adds a segment of given half thickness, measuring distance from pixel centers
+    fn add_thick_segment(
+        &mut self,
+        start: (f32, f32),
+        end: (f32, f32),
+        half_thickness: f32,
+        start_cap: LineCap,
+        end_cap: LineCap,
+        anti_aliased: bool,
+    ) {
+        let margin = half_thickness + 1.0;
+
+        // Ends cut off here lie far beyond mask, so their caps never show
+        let Some((clipped_start, clipped_end)) =
+            clip_segment(start, end, &self.bounds, CLIP_MARGIN + margin)
+        else {
+            return;
+        };
+
+        let start_cap = if clipped_start == start {
+            start_cap
+        } else {
+            LineCap::Butt
+        };
+        let end_cap = if clipped_end == end {
+            end_cap
+        } else {
+            LineCap::Butt
+        };
+        let (start, end) = (clipped_start, clipped_end);
+
+        let (delta_x, delta_y) = (end.0 - start.0, end.1 - start.1);
+        let length = (delta_x * delta_x + delta_y * delta_y).sqrt();
+
+        if length == 0.0 {
+            if start_cap == LineCap::Round || end_cap == LineCap::Round {
+                self.add_disk(start, half_thickness, anti_aliased);
+            }
+
+            return;
+        }
+
+        let (direction_x, direction_y) = (delta_x / length, delta_y / length);
+
+        let lower = match start_cap {
+            LineCap::Square => -half_thickness,
+            _ => 0.0,
+        };
+        let upper = match end_cap {
+            LineCap::Square => length + half_thickness,
+            _ => length,
+        };
+
+        // Segment grown by margin on every side, so each row only visits pixels near stroke
+        let (normal_x, normal_y) = (-direction_y, direction_x);
+        let corners = [
+            (
+                start.0 - (direction_x + normal_x) * margin,
+                start.1 - (direction_y + normal_y) * margin,
+            ),
+            (
+                start.0 - (direction_x - normal_x) * margin,
+                start.1 - (direction_y - normal_y) * margin,
+            ),
+            (
+                end.0 + (direction_x + normal_x) * margin,
+                end.1 + (direction_y + normal_y) * margin,
+            ),
+            (
+                end.0 + (direction_x - normal_x) * margin,
+                end.1 + (direction_y - normal_y) * margin,
+            ),
+        ];
+
+        let (top, bottom) = corners
+            .iter()
+            .fold((f32::MAX, f32::MIN), |(top, bottom), &(_, y)| {
+                (top.min(y), bottom.max(y))
+            });
+        let first_row = (top.floor() as i32).max(self.bounds.y);
+        let last_row = (bottom.ceil() as i32).min(self.bounds.bottom() - 1);
+
+        for y in first_row..=last_row {
+            let Some((left, right)) = row_span(&corners, y as f32) else {
+                continue;
+            };
+
+            let first_x = (left.floor() as i32 - 1).max(self.bounds.x);
+            let last_x = (right.ceil() as i32 + 1).min(self.bounds.right() - 1);
+
+            for x in first_x..=last_x {
+                let (offset_x, offset_y) = (x as f32 - start.0, y as f32 - start.1);
+                let along = offset_x * direction_x + offset_y * direction_y;
+                let across = offset_x * direction_y - offset_y * direction_x;
+
+                let round_end = if along < 0.0 && start_cap == LineCap::Round {
+                    Some((offset_x, offset_y))
+                } else if along > length && end_cap == LineCap::Round {
+                    Some((x as f32 - end.0, y as f32 - end.1))
+                } else {
+                    None
+                };
+
+                let value = match (round_end, anti_aliased) {
+                    (Some((end_x, end_y)), true) => {
+                        let distance = (end_x * end_x + end_y * end_y).sqrt();
+                        (half_thickness - distance + 0.5).clamp(0.0, 1.0)
+                    }
+                    (Some((end_x, end_y)), false) => {
+                        let inside =
+                            end_x * end_x + end_y * end_y < half_thickness * half_thickness;
+                        inside as u8 as f32
+                    }
+                    (None, true) => {
+                        let side = (half_thickness - across.abs() + 0.5).clamp(0.0, 1.0);
+                        let start_edge = match start_cap {
+                            LineCap::Round => 1.0,
+                            _ => (along - lower + 0.5).clamp(0.0, 1.0),
+                        };
+                        let end_edge = match end_cap {
+                            LineCap::Round => 1.0,
+                            _ => (upper - along + 0.5).clamp(0.0, 1.0),
+                        };
+
+                        side * start_edge.min(end_edge)
+                    }
+                    // Half-open bounds keep integer thickness exactly that many pixels wide
+                    (None, false) => {
+                        let inside = across >= -half_thickness
+                            && across < half_thickness
+                            && along >= lower
+                            && along < upper;
+                        inside as u8 as f32
+                    }
+                };
+
+                self.add(x, y, value);
+            }
+        }
+    }
+
+    // Adds a filled disk, used for round caps and joins
+    fn add_disk(&mut self, center: (f32, f32), radius: f32, anti_aliased: bool) {
+        let reach = radius.ceil() as i32 + 1;
+        let grown = radius + 2.0;
+
+        // Skipped while far away, where rounding center to integers could overflow
+        if center.0 < self.bounds.x as f32 - grown
+            || center.1 < self.bounds.y as f32 - grown
+            || center.0 > self.bounds.right() as f32 + grown
+            || center.1 > self.bounds.bottom() as f32 + grown
+        {
+            return;
+        }
+
+        let (center_x, center_y) = (center.0.round() as i32, center.1.round() as i32);
+
+        for y in center_y - reach..=center_y + reach {
+            for x in center_x - reach..=center_x + reach {
+                let (offset_x, offset_y) = (x as f32 - center.0, y as f32 - center.1);
+                let distance = (offset_x * offset_x + offset_y * offset_y).sqrt();
+
+                let value = if anti_aliased {
+                    (radius - distance + 0.5).clamp(0.0, 1.0)
+                } else {
+                    (distance < radius) as u8 as f32
+                };
+
+                self.add(x, y, value);
+            }
+        }
+    }
+}
+
+// Cuts a segment to a rectangle grown by margin, returning None if it misses it entirely
+fn clip_segment(
+    start: (f32, f32),
+    end: (f32, f32),
+    rect: &Rect,
+    margin: f32,
+) -> Option<((f32, f32), (f32, f32))> {
+    let (delta_x, delta_y) = (end.0 - start.0, end.1 - start.1);
+    let (mut enter, mut exit) = (0.0_f32, 1.0_f32);
+
+    // Liang-Barsky: each side limits the part of segment inside it
+    let sides = [
+        (-delta_x, start.0 - (rect.x as f32 - margin)),
+        (delta_x, rect.right() as f32 + margin - start.0),
+        (-delta_y, start.1 - (rect.y as f32 - margin)),
+        (delta_y, rect.bottom() as f32 + margin - start.1),
+    ];
+
+    for (direction, distance) in sides {
+        if direction == 0.0 {
+            if distance < 0.0 {
+                return None;
+            }
+
+            continue;
+        }
+
+        let crossing = distance / direction;
+
+        if direction < 0.0 {
+            enter = enter.max(crossing);
+        } else {
+            exit = exit.min(crossing);
+        }
+
+        if enter > exit {
+            return None;
+        }
+    }
+
+    // Untouched ends are returned exactly, so fully visible segments rasterize as before
+    let point_at = |fraction: f32, original: (f32, f32)| match fraction {
+        0.0 => start,
+        1.0 => end,
+        _ => (
+            original.0 + fraction * delta_x,
+            original.1 + fraction * delta_y,
+        ),
+    };
+
+    Some((point_at(enter, start), point_at(exit, start)))
+}
+
+// Returns leftmost and rightmost x where a row crosses a convex polygon
+fn row_span(corners: &[(f32, f32)], y: f32) -> Option<(f32, f32)> {
+    let mut span: Option<(f32, f32)> = None;
+
+    for (index, &(start_x, start_y)) in corners.iter().enumerate() {
+        let (end_x, end_y) = corners[(index + 1) % corners.len()];
+
+        if y < start_y.min(end_y) || y > start_y.max(end_y) {
+            continue;
+        }
+
+        let (left, right) = if start_y == end_y {
+            (start_x.min(end_x), start_x.max(end_x))
+        } else {
+            let x = start_x + (y - start_y) * (end_x - start_x) / (end_y - start_y);
+            (x, x)
+        };
+
+        span = Some(match span {
+            Some((span_left, span_right)) => (span_left.min(left), span_right.max(right)),
+            None => (left, right),
+        });
+    }
+
+    span
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn covered_pixels(renderer: &Renderer) -> usize {
+        renderer
+            .pixels()
+            .iter()
+            .filter(|&&pixel| pixel & 0x00FF_FFFF != 0)
+            .count()
+    }
+
+    #[test]
+    fn far_off_screen_endpoints_are_clipped() {
+        let mut renderer = Renderer::offscreen(64, 64);
+        let far = 3.0e9;
+
+        for (thickness, anti_aliased) in [(1.0, false), (1.0, true), (5.0, false), (5.0, true)] {
+            let style = LineStyle::new()
+                .thickness(thickness)
+                .cap(LineCap::Round)
+                .anti_aliased(anti_aliased);
+
+            renderer.draw_line_styled(-far, -far, far, far, &style, [255, 255, 255, 255]);
+            renderer.draw_polyline(
+                &[(-far, 32.0), (32.0, 32.0), (32.0, far)],
+                &style,
+                [255, 255, 255, 255],
+            );
+        }
+
+        assert!(renderer.pixel(10, 10).is_some_and(|[r, ..]| r > 0));
+        assert!(renderer.pixel(0, 32).is_some_and(|[r, ..]| r > 0));
+        assert!(renderer.pixel(32, 63).is_some_and(|[r, ..]| r > 0));
+    }
+
+    #[test]
+    fn diagonal_square_caps_keep_their_corners() {
+        let mut renderer = Renderer::offscreen(64, 64);
+        let style = LineStyle::new().thickness(20.0).cap(LineCap::Square);
+
+        renderer.draw_line_styled(30.0, 30.0, 50.0, 50.0, &style, [255, 255, 255, 255]);
+
+        // Cap corners reach further out than endpoints grown by half thickness
+        assert!(renderer.pixel(17, 30).is_some_and(|[r, ..]| r > 0));
+        assert!(renderer.pixel(63, 50).is_some_and(|[r, ..]| r > 0));
+    }
+
+    #[test]
+    fn thick_line_covers_same_pixels_in_both_directions() {
+        let style = LineStyle::new().thickness(6.0).cap(LineCap::Round);
+        let mut forward = Renderer::offscreen(64, 64);
+        let mut backward = Renderer::offscreen(64, 64);
+
+        forward.draw_line_styled(4.0, 10.0, 58.0, 50.0, &style, [255, 255, 255, 255]);
+        backward.draw_line_styled(58.0, 50.0, 4.0, 10.0, &style, [255, 255, 255, 255]);
+
+        assert!(covered_pixels(&forward) > 0);
+        assert_eq!(covered_pixels(&forward), covered_pixels(&backward));
+    }
+}
@@ -1,5 +1,34 @@
+mod blend;
+mod camera;
+mod canvas;
+mod curves;
+mod damage;
 mod error;
+mod indexed_canvas;
+mod lines;
+mod nine_slice;
+mod params;
+mod post_process;
+mod queue;
+mod raster;
+mod recording;
+mod rect;
 mod renderer;
+mod shapes;
+mod tile_map;
 
+pub use blend::BlendMode;
+pub use camera::Camera2D;
+pub use canvas::Canvas;
 pub use error::RendererError;
+pub use indexed_canvas::IndexedCanvas;
+pub use lines::{LineCap, LineStyle};
+pub use nine_slice::{Insets, SliceFill};
+pub use params::DrawParams;
+pub use post_process::{
+    Bloom, ChromaticAberration, ColorLut, Crt, Grayscale, PostEffect, Scanlines, Sepia,
+};
+pub use queue::DeferredFrame;
+pub use recording::{RecordingConfiguration, RecordingFormat};
+pub use rect::Rect;
 pub use renderer::Renderer;
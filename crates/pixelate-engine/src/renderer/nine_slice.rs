@@ -0,0 +1,272 @@
+use super::{Rect, Renderer};
+use crate::assets::ImageData;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Insets {
+    pub left: u32,
+    pub top: u32,
+    pub right: u32,
+    pub bottom: u32,
+}
+
+impl Insets {
+    // Creates insets with a distance for each side
+    pub const fn new(left: u32, top: u32, right: u32, bottom: u32) -> Self {
+        Self {
+            left,
+            top,
+            right,
+            bottom,
+        }
+    }
+
+    // Creates insets with same distance on all sides
+    pub const fn uniform(inset: u32) -> Self {
+        Self::new(inset, inset, inset, inset)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SliceFill {
+    // Scales edges and center to fill their space
+    #[default]
+    Stretch,
+    // Repeats edges and center at their own size, cropping last repeat
+    Tile,
+}
+
+impl Renderer {
+    // Draws an image as a scalable frame, keeping corners intact and stretching edges and center
+    pub fn draw_nine_slice(&mut self, image: &ImageData, insets: Insets, destination: Rect) {
+        self.draw_nine_slice_ex(image, insets, destination, SliceFill::Stretch);
+    }
+
+    // Draws an image as a scalable frame, filling edges and center as given
+    pub fn draw_nine_slice_ex(
+        &mut self,
+        image: &ImageData,
+        insets: Insets,
+        destination: Rect,
+        fill: SliceFill,
+    ) {
+        let source_columns = slice_bands(image.width, insets.left, insets.right);
+        let source_rows = slice_bands(image.height, insets.top, insets.bottom);
+
+        let Some((source_columns, source_rows)) = source_columns.zip(source_rows) else {
+            return;
+        };
+
+        let columns = fit_bands(destination.x, destination.width, source_columns);
+        let rows = fit_bands(destination.y, destination.height, source_rows);
+
+        for (row, (source_y, source_height)) in source_rows.into_iter().enumerate() {
+            for (column, (source_x, source_width)) in source_columns.into_iter().enumerate() {
+                let (x, width) = columns[column];
+                let (y, height) = rows[row];
+
+                let source = Rect::new(source_x, source_y, source_width, source_height);
+                let target = Rect::new(x, y, width, height);
+
+                // Corners always keep their size, only shrinking when destination is too small
+                let is_corner = row != 1 && column != 1;
+
+                match fill {
+                    SliceFill::Tile if !is_corner => self.tile_region(image, source, target),
+                    _ => self.draw_region_of(image, source, target),
+                }
+            }
+        }
+    }
+
+    // Repeats a source rectangle across a destination at its own size
+    fn tile_region(&mut self, image: &ImageData, source: Rect, destination: Rect) {
+        if source.is_empty() {
+            return;
+        }
+
+        let mut offset_y = 0;
+
+        while offset_y < destination.height {
+            let height = source.height.min(destination.height - offset_y);
+            let mut offset_x = 0;
+
+            while offset_x < destination.width {
+                let width = source.width.min(destination.width - offset_x);
+
+                self.draw_region_of(
+                    image,
+                    Rect::new(source.x, source.y, width, height),
+                    Rect::new(
+                        destination.x + offset_x as i32,
+                        destination.y + offset_y as i32,
+                        width,
+                        height,
+                    ),
+                );
+
+                offset_x += width;
+            }
+
+            offset_y += height;
+        }
+    }
+}
+
+// Splits a source length into start, middle and end bands, or None if insets overlap
+fn slice_bands(length: u32, start: u32, end: u32) -> Option<[(i32, u32); 3]> {
+    let middle = length.checked_sub(start)?.checked_sub(end)?;
+
+    Some([
+        (0, start),
+        (start as i32, middle),
+        ((start + middle) as i32, end),
+    ])
+}
+
+// Places bands along a destination span, shrinking outer bands proportionally when they don't fit
+fn fit_bands(position: i32, length: u32, bands: [(i32, u32); 3]) -> [(i32, u32); 3] {
+    let (start, end) = (bands[0].1, bands[2].1);
+    let outer = start + end;
+
+    let (start, end) = match outer > length {
+        true => {
+            let start = (start as u64 * length as u64 / outer as u64) as u32;
+            (start, length - start)
+        }
+        false => (start, end),
+    };
+
+    let middle = length - start - end;
+
+    [
+        (position, start),
+        (position + start as i32, middle),
+        (position + (start + middle) as i32, end),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Creates an opaque image whose pixels encode their own position
+    fn position_image(width: u32, height: u32) -> ImageData {
+        let bytes = (0..height)
+            .flat_map(|y| (0..width).flat_map(move |x| [x as u8 * 16, y as u8 * 16, 0, 255]))
+            .collect();
+
+        ImageData::from_bytes(bytes, width, height)
+    }
+
+    // Returns source position a drawn pixel was copied from, or None if nothing was drawn
+    fn source_at(renderer: &Renderer, x: i32, y: i32) -> Option<(i32, i32)> {
+        let [r, g, _, a] = renderer.pixel(x, y)?;
+
+        (a == 255).then_some((r as i32 / 16, g as i32 / 16))
+    }
+
+    // Asserts a destination area is a pixel-exact copy of a source area
+    fn assert_copied(renderer: &Renderer, destination: (i32, i32), source: Rect) {
+        for y in 0..source.height as i32 {
+            for x in 0..source.width as i32 {
+                assert_eq!(
+                    source_at(renderer, destination.0 + x, destination.1 + y),
+                    Some((source.x + x, source.y + y)),
+                    "pixel {:?}",
+                    (destination.0 + x, destination.1 + y)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn stretch_keeps_corners_exact_and_scales_middle() {
+        let image = position_image(6, 6);
+        let mut renderer = Renderer::offscreen(16, 16);
+
+        renderer.draw_nine_slice(&image, Insets::uniform(2), Rect::new(1, 1, 12, 10));
+
+        assert_copied(&renderer, (1, 1), Rect::new(0, 0, 2, 2));
+        assert_copied(&renderer, (11, 1), Rect::new(4, 0, 2, 2));
+        assert_copied(&renderer, (1, 9), Rect::new(0, 4, 2, 2));
+        assert_copied(&renderer, (11, 9), Rect::new(4, 4, 2, 2));
+
+        // Two source columns stretch over eight and two rows over six
+        for y in 3..9 {
+            for x in 3..11 {
+                let expected = (2 + (x - 3) / 4, 2 + (y - 3) / 3);
+                assert_eq!(source_at(&renderer, x, y), Some(expected));
+            }
+        }
+
+        assert_eq!(source_at(&renderer, 0, 0), None);
+        assert_eq!(source_at(&renderer, 13, 11), None);
+    }
+
+    #[test]
+    fn tile_repeats_middle_and_crops_remainder() {
+        let image = position_image(6, 6);
+        let mut renderer = Renderer::offscreen(16, 16);
+
+        // Middle spans of seven and five leave a one pixel remainder of a two pixel band
+        renderer.draw_nine_slice_ex(
+            &image,
+            Insets::uniform(2),
+            Rect::new(0, 0, 11, 9),
+            SliceFill::Tile,
+        );
+
+        assert_copied(&renderer, (0, 0), Rect::new(0, 0, 2, 2));
+        assert_copied(&renderer, (9, 7), Rect::new(4, 4, 2, 2));
+
+        for y in 2..7 {
+            for x in 2..9 {
+                let expected = (2 + (x - 2) % 2, 2 + (y - 2) % 2);
+                assert_eq!(source_at(&renderer, x, y), Some(expected));
+            }
+        }
+
+        for x in 2..9 {
+            assert_eq!(source_at(&renderer, x, 0), Some((2 + (x - 2) % 2, 0)));
+            assert_eq!(source_at(&renderer, x, 8), Some((2 + (x - 2) % 2, 5)));
+        }
+
+        assert_eq!(source_at(&renderer, 11, 0), None);
+        assert_eq!(source_at(&renderer, 0, 9), None);
+    }
+
+    #[test]
+    fn corners_shrink_to_fit_small_destination() {
+        let image = position_image(6, 6);
+        let mut renderer = Renderer::offscreen(8, 8);
+
+        // Corners of two don't fit in three, so start keeps one pixel and end keeps two
+        renderer.draw_nine_slice(&image, Insets::uniform(2), Rect::new(2, 2, 3, 3));
+
+        assert_copied(&renderer, (3, 3), Rect::new(4, 4, 2, 2));
+        assert!(source_at(&renderer, 2, 2).is_some());
+
+        for y in 0..8 {
+            for x in 0..8 {
+                let inside = (2..5).contains(&x) && (2..5).contains(&y);
+                assert_eq!(source_at(&renderer, x, y).is_some(), inside);
+            }
+        }
+    }
+
+    #[test]
+    fn insets_larger_than_source_draw_nothing() {
+        let image = position_image(6, 6);
+        let mut renderer = Renderer::offscreen(8, 8);
+
+        renderer.draw_nine_slice(&image, Insets::new(4, 0, 3, 0), Rect::new(0, 0, 8, 8));
+        renderer.draw_nine_slice_ex(
+            &image,
+            Insets::new(0, 3, 0, 4),
+            Rect::new(0, 0, 8, 8),
+            SliceFill::Tile,
+        );
+
+        assert!(renderer.pixels().iter().all(|&pixel| pixel == 0));
+    }
+}
@@ -0,0 +1,96 @@
+use super::Rect;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DrawParams {
+    pub(crate) source: Option<Rect>,
+    pub(crate) scale: (f32, f32),
+    pub(crate) rotation: f32,
+    pub(crate) quarter_turns: u8,
+    pub(crate) pivot: (f32, f32),
+    pub(crate) flip_x: bool,
+    pub(crate) flip_y: bool,
+}
+
+impl DrawParams {
+    // Creates parameters that draw a whole image unscaled with its top-left at position
+    pub fn new() -> Self {
+        Self {
+            source: None,
+            scale: (1.0, 1.0),
+            rotation: 0.0,
+            quarter_turns: 0,
+            pivot: (0.0, 0.0),
+            flip_x: false,
+            flip_y: false,
+        }
+    }
+
+    // Restricts drawing to a sub-rectangle of image
+    pub fn source(mut self, source: Rect) -> Self {
+        self.source = Some(source);
+        self
+    }
+
+    // Sets independent horizontal and vertical scale factors
+    pub fn scale(mut self, scale_x: f32, scale_y: f32) -> Self {
+        self.scale = (scale_x, scale_y);
+        self
+    }
+
+    // Sets clockwise rotation around pivot in radians
+    pub fn rotation(mut self, radians: f32) -> Self {
+        self.rotation = radians;
+        self
+    }
+
+    // Sets clockwise rotation in exact 90 degree steps, applied on top of rotation
+    pub fn quarter_turns(mut self, turns: u8) -> Self {
+        self.quarter_turns = turns % 4;
+        self
+    }
+
+    // Sets pivot in unscaled source pixels; pivot is placed at draw position
+    pub fn pivot(mut self, pivot_x: f32, pivot_y: f32) -> Self {
+        self.pivot = (pivot_x, pivot_y);
+        self
+    }
+
+    // Mirrors image horizontally
+    pub fn flip_x(mut self, flip_x: bool) -> Self {
+        self.flip_x = flip_x;
+        self
+    }
+
+    // Mirrors image vertically
+    pub fn flip_y(mut self, flip_y: bool) -> Self {
+        self.flip_y = flip_y;
+        self
+    }
+
+    // Returns sine and cosine of combined rotation, exact for pure quarter turns
+    pub(crate) fn rotation_sin_cos(&self) -> (f32, f32) {
+        let (quarter_sin, quarter_cos) = match self.quarter_turns {
+            0 => (0.0, 1.0),
+            1 => (1.0, 0.0),
+            2 => (0.0, -1.0),
+            _ => (-1.0, 0.0),
+        };
+
+        if self.rotation == 0.0 {
+            return (quarter_sin, quarter_cos);
+        }
+
+        let (sin, cos) = self.rotation.sin_cos();
+
+        (
+            sin * quarter_cos + cos * quarter_sin,
+            cos * quarter_cos - sin * quarter_sin,
+        )
+    }
+}
+
+impl Default for DrawParams {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,480 @@
+use super::{
+    blend::{pack, unpack},
+    raster,
+};
+use crate::assets::ImageData;
+
+// Effect run over finished frame before it is presented; rows may be processed on several threads
+pub trait PostEffect: Sync {
+    // Returns new color of a single pixel; used by default apply
+    fn process_pixel(&self, _x: u32, _y: u32, color: [u8; 4]) -> [u8; 4] {
+        color
+    }
+
+    // Checks if effect only uses process_pixel, so output pixels depend on nothing but input
+    // pixel at same position; lets unchanged parts of a frame skip reprocessing
+    fn is_per_pixel(&self) -> bool {
+        false
+    }
+
+    // Runs effect over a whole frame of packed ARGB pixels in row-major order
+    fn apply(&mut self, pixels: &mut [u32], width: u32, height: u32) {
+        let effect = &*self;
+
+        raster::for_each_row(pixels, width, 0..height as i32, width, |y, row| {
+            for (x, pixel) in row.iter_mut().enumerate() {
+                let [r, g, b, a] = effect.process_pixel(x as u32, y as u32, unpack(*pixel));
+                *pixel = pack(r, g, b, a);
+            }
+        });
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Scanlines {
+    pub intensity: f32,
+    pub spacing: u32,
+}
+
+impl Default for Scanlines {
+    // Creates scanlines darkening every other row by a third
+    fn default() -> Self {
+        Self {
+            intensity: 0.35,
+            spacing: 2,
+        }
+    }
+}
+
+impl PostEffect for Scanlines {
+    fn is_per_pixel(&self) -> bool {
+        true
+    }
+
+    fn process_pixel(&self, _x: u32, y: u32, color: [u8; 4]) -> [u8; 4] {
+        let spacing = self.spacing.max(1);
+
+        if y % spacing != spacing - 1 {
+            return color;
+        }
+
+        scale_color(color, 1.0 - self.intensity.clamp(0.0, 1.0))
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Crt {
+    pub curvature: f32,
+    pub vignette: f32,
+}
+
+impl Default for Crt {
+    // Creates a gently curved screen with darkened corners
+    fn default() -> Self {
+        Self {
+            curvature: 0.08,
+            vignette: 0.35,
+        }
+    }
+}
+
+impl PostEffect for Crt {
+    fn apply(&mut self, pixels: &mut [u32], width: u32, height: u32) {
+        let source = pixels.to_vec();
+        let (half_width, half_height) = (width as f32 / 2.0, height as f32 / 2.0);
+        let Crt {
+            curvature,
+            vignette,
+        } = *self;
+
+        raster::for_each_row(pixels, width, 0..height as i32, width, |y, row| {
+            for (x, pixel) in row.iter_mut().enumerate() {
+                let u = (x as f32 + 0.5) / half_width - 1.0;
+                let v = (y as f32 + 0.5) / half_height - 1.0;
+                let distance_squared = u * u + v * v;
+
+                // Barrel distortion samples further out the further a pixel is from center
+                let bulge = 1.0 + curvature * distance_squared;
+                let (source_u, source_v) = (u * bulge, v * bulge);
+
+                if source_u.abs() > 1.0 || source_v.abs() > 1.0 {
+                    *pixel = pack(0, 0, 0, 255);
+                    continue;
+                }
+
+                let source_x = (((source_u + 1.0) * half_width) as u32).min(width - 1);
+                let source_y = (((source_v + 1.0) * half_height) as u32).min(height - 1);
+                let color = unpack(source[(source_y * width + source_x) as usize]);
+
+                // Corners sit at a squared distance of 2 from center
+                let shade = 1.0 - vignette * distance_squared / 2.0;
+                let [r, g, b, a] = scale_color(color, shade.clamp(0.0, 1.0));
+
+                *pixel = pack(r, g, b, a);
+            }
+        });
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ChromaticAberration {
+    pub strength: f32,
+}
+
+impl Default for ChromaticAberration {
+    // Creates a split of two pixels at screen edges
+    fn default() -> Self {
+        Self { strength: 2.0 }
+    }
+}
+
+impl PostEffect for ChromaticAberration {
+    fn apply(&mut self, pixels: &mut [u32], width: u32, height: u32) {
+        let source = pixels.to_vec();
+        let (half_width, half_height) = (width as f32 / 2.0, height as f32 / 2.0);
+
+        let sample = |x: f32, y: f32| {
+            let x = (x.round().max(0.0) as u32).min(width - 1);
+            let y = (y.round().max(0.0) as u32).min(height - 1);
+            unpack(source[(y * width + x) as usize])
+        };
+
+        let strength = self.strength;
+
+        raster::for_each_row(pixels, width, 0..height as i32, width, |y, row| {
+            for (x, pixel) in row.iter_mut().enumerate() {
+                let (x, y) = (x as f32, y as f32);
+
+                // Red shifts outwards and blue inwards, more so towards edges
+                let offset_x = (x + 0.5 - half_width) / half_width * strength;
+                let offset_y = (y + 0.5 - half_height) / half_height * strength;
+
+                let [_, g, _, a] = unpack(*pixel);
+                let r = sample(x - offset_x, y - offset_y)[0];
+                let b = sample(x + offset_x, y + offset_y)[2];
+
+                *pixel = pack(r, g, b, a);
+            }
+        });
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Bloom {
+    pub threshold: u8,
+    pub intensity: f32,
+    pub radius: u32,
+}
+
+impl Default for Bloom {
+    // Creates a soft glow around bright pixels
+    fn default() -> Self {
+        Self {
+            threshold: 200,
+            intensity: 0.6,
+            radius: 4,
+        }
+    }
+}
+
+impl PostEffect for Bloom {
+    fn apply(&mut self, pixels: &mut [u32], width: u32, height: u32) {
+        let (columns, rows) = (width as usize, height as usize);
+        let radius = self.radius as usize;
+
+        // Keep only pixels bright enough to glow
+        let bright: Vec<[u32; 3]> = pixels
+            .iter()
+            .map(|&pixel| {
+                let [r, g, b, _] = unpack(pixel);
+                let luminance = (r as u32 * 77 + g as u32 * 150 + b as u32 * 29) >> 8;
+
+                match luminance >= self.threshold as u32 {
+                    true => [r as u32, g as u32, b as u32],
+                    false => [0, 0, 0],
+                }
+            })
+            .collect();
+
+        let mut horizontal = vec![[0; 3]; bright.len()];
+        let mut glow = vec![[0; 3]; bright.len()];
+
+        box_blur(&bright, &mut horizontal, columns, 1, rows, columns, radius);
+        box_blur(&horizontal, &mut glow, rows, columns, columns, 1, radius);
+
+        let intensity = self.intensity;
+        let add =
+            |channel: u8, glow: u32| (channel as f32 + glow as f32 * intensity).min(255.0) as u8;
+
+        raster::for_each_row(pixels, width, 0..height as i32, width, |y, row| {
+            let glow_row = &glow[y as usize * columns..(y as usize + 1) * columns];
+
+            for (pixel, glow) in row.iter_mut().zip(glow_row) {
+                let [r, g, b, a] = unpack(*pixel);
+                *pixel = pack(add(r, glow[0]), add(g, glow[1]), add(b, glow[2]), a);
+            }
+        });
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Grayscale {
+    pub amount: f32,
+}
+
+impl Default for Grayscale {
+    fn default() -> Self {
+        Self { amount: 1.0 }
+    }
+}
+
+impl PostEffect for Grayscale {
+    fn is_per_pixel(&self) -> bool {
+        true
+    }
+
+    fn process_pixel(&self, _x: u32, _y: u32, color: [u8; 4]) -> [u8; 4] {
+        let [r, g, b, a] = color;
+        let luminance = r as f32 * 0.299 + g as f32 * 0.587 + b as f32 * 0.114;
+
+        mix_color(
+            color,
+            [luminance, luminance, luminance, a as f32],
+            self.amount,
+        )
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Sepia {
+    pub amount: f32,
+}
+
+impl Default for Sepia {
+    fn default() -> Self {
+        Self { amount: 1.0 }
+    }
+}
+
+impl PostEffect for Sepia {
+    fn is_per_pixel(&self) -> bool {
+        true
+    }
+
+    fn process_pixel(&self, _x: u32, _y: u32, color: [u8; 4]) -> [u8; 4] {
+        let [r, g, b, a] = color.map(|channel| channel as f32);
+
+        let toned = [
+            r * 0.393 + g * 0.769 + b * 0.189,
+            r * 0.349 + g * 0.686 + b * 0.168,
+            r * 0.272 + g * 0.534 + b * 0.131,
+            a,
+        ];
+
+        mix_color(color, toned, self.amount)
+    }
+}
+
+// Color grading through a 3D lookup table stored as an image of blue slices
+#[derive(Clone, Debug, PartialEq)]
+pub struct ColorLut {
+    size: usize,
+    table: Vec<[f32; 3]>,
+    pub amount: f32,
+}
+
+impl ColorLut {
+    // Reads a lookup table laid out as square blue slices of red by green, left-to-right, top-to-bottom
+    // (e.g. a 256x16 strip for 16 levels or a 512x512 grid for 64 levels)
+    pub fn from_image(image: &ImageData) -> Option<Self> {
+        let (width, height) = (image.width as usize, image.height as usize);
+        let size = ((width * height) as f64).cbrt().round() as usize;
+
+        if size < 2 || size * size * size != width * height || width % size != 0 {
+            return None;
+        }
+
+        let columns = width / size;
+        let mut table = Vec::with_capacity(size * size * size);
+
+        for blue in 0..size {
+            let (tile_x, tile_y) = ((blue % columns) * size, (blue / columns) * size);
+
+            for green in 0..size {
+                for red in 0..size {
+                    let [r, g, b, _] =
+                        image.pixel((tile_x + red) as i32, (tile_y + green) as i32)?;
+                    table.push([r as f32, g as f32, b as f32]);
+                }
+            }
+        }
+
+        Some(Self {
+            size,
+            table,
+            amount: 1.0,
+        })
+    }
+
+    // Returns number of levels per channel
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    // Returns graded color, interpolating between the eight surrounding table entries
+    fn lookup(&self, color: [u8; 4]) -> [f32; 3] {
+        let scale = (self.size - 1) as f32 / 255.0;
+
+        let split = |channel: u8| {
+            let position = channel as f32 * scale;
+            let low = position.floor() as usize;
+            (low, (low + 1).min(self.size - 1), position - low as f32)
+        };
+
+        let (r0, r1, r_weight) = split(color[0]);
+        let (g0, g1, g_weight) = split(color[1]);
+        let (b0, b1, b_weight) = split(color[2]);
+
+        let entry = |r: usize, g: usize, b: usize| self.table[(b * self.size + g) * self.size + r];
+        let lerp = |from: [f32; 3], to: [f32; 3], weight: f32| {
+            [0, 1, 2].map(|channel| from[channel] + (to[channel] - from[channel]) * weight)
+        };
+
+        let along_red = |g: usize, b: usize| lerp(entry(r0, g, b), entry(r1, g, b), r_weight);
+        let along_green = |b: usize| lerp(along_red(g0, b), along_red(g1, b), g_weight);
+
+        lerp(along_green(b0), along_green(b1), b_weight)
+    }
+}
+
+impl PostEffect for ColorLut {
+    fn is_per_pixel(&self) -> bool {
+        true
+    }
+
+    fn process_pixel(&self, _x: u32, _y: u32, color: [u8; 4]) -> [u8; 4] {
+        let [r, g, b] = self.lookup(color);
+
+        mix_color(color, [r, g, b, color[3] as f32], self.amount)
+    }
+}
+
+// Scales RGB channels of a color by a factor, keeping alpha
+fn scale_color(color: [u8; 4], factor: f32) -> [u8; 4] {
+    let [r, g, b, a] = color;
+    let scale = |channel: u8| (channel as f32 * factor).clamp(0.0, 255.0) as u8;
+
+    [scale(r), scale(g), scale(b), a]
+}
+
+// Blends a color towards a target by amount in range 0 to 1
+fn mix_color(color: [u8; 4], target: [f32; 4], amount: f32) -> [u8; 4] {
+    let amount = amount.clamp(0.0, 1.0);
+
+    [0, 1, 2, 3].map(|channel| {
+        let from = color[channel] as f32;
+        (from + (target[channel] - from) * amount)
+            .round()
+            .clamp(0.0, 255.0) as u8
+    })
+}
+
+// Averages values within radius along lines of a grid, treating outside values as zero
+fn box_blur(
+    source: &[[u32; 3]],
+    target: &mut [[u32; 3]],
+    length: usize,
+    step: usize,
+    lines: usize,
+    line_step: usize,
+    radius: usize,
+) {
+    let window = (2 * radius + 1) as u32;
+
+    for line in 0..lines {
+        let at = |index: usize| line * line_step + index * step;
+        let mut sum = [0u32; 3];
+
+        for index in 0..=radius.min(length.saturating_sub(1)) {
+            for (total, value) in sum.iter_mut().zip(source[at(index)]) {
+                *total += value;
+            }
+        }
+
+        for index in 0..length {
+            target[at(index)] = sum.map(|channel| channel / window);
+
+            if index + radius + 1 < length {
+                for (total, value) in sum.iter_mut().zip(source[at(index + radius + 1)]) {
+                    *total += value;
+                }
+            }
+
+            if index >= radius {
+                for (total, value) in sum.iter_mut().zip(source[at(index - radius)]) {
+                    *total -= value;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::renderer::{Rect, Renderer};
+
+    const RED: [u8; 4] = [200, 0, 0, 255];
+
+    #[test]
+    fn effects_leave_drawn_pixels_untouched_and_do_not_compound() {
+        let mut renderer = Renderer::offscreen(4, 4);
+        renderer.add_post_effect(Grayscale { amount: 0.5 });
+        renderer.clear(RED);
+
+        renderer.present().unwrap();
+        let first = renderer.screenshot();
+
+        // An on-demand redraw presents again without drawing anything new
+        renderer.invalidate();
+        renderer.present().unwrap();
+
+        assert_eq!(renderer.pixel(0, 0), Some(RED));
+        assert_eq!(renderer.screenshot().rgba_bytes, first.rgba_bytes);
+        assert_ne!(first.pixel(0, 0), Some(RED));
+    }
+
+    #[test]
+    fn per_pixel_effects_keep_damage_to_drawn_regions() {
+        let mut renderer = Renderer::offscreen(8, 8);
+        renderer.add_post_effect(Sepia::default());
+        renderer.clear(RED);
+        renderer.present().unwrap();
+        let untouched = renderer.screenshot();
+
+        renderer.draw_rectangle(0, 0, 2, 2, [0, 0, 200, 255]);
+        renderer.apply_post_effects();
+
+        assert_eq!(renderer.damaged_regions(), vec![Rect::new(0, 0, 2, 2)]);
+
+        renderer.present().unwrap();
+        let screenshot = renderer.screenshot();
+
+        assert_eq!(screenshot.pixel(5, 5), untouched.pixel(5, 5));
+        assert_ne!(screenshot.pixel(0, 0), untouched.pixel(0, 0));
+    }
+
+    #[test]
+    fn removing_effects_shows_drawn_frame_again() {
+        let mut renderer = Renderer::offscreen(4, 4);
+        renderer.add_post_effect(Crt::default());
+        renderer.clear(RED);
+        renderer.present().unwrap();
+        renderer.present().unwrap();
+
+        renderer.clear_post_effects();
+        renderer.present().unwrap();
+
+        assert_eq!(renderer.screenshot().pixel(1, 1), Some(RED));
+    }
+}
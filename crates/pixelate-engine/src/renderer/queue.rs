@@ -0,0 +1,154 @@
+use super::{BlendMode, Camera2D, Rect, Renderer};
+use std::ops::{Deref, DerefMut};
+
+// Recorded draw call, replayed against renderer when its frame ends
+type DrawFn<'frame> = Box<dyn FnOnce(&mut Renderer) + 'frame>;
+
+// Renderer settings a queued draw call was submitted under
+pub(crate) struct DrawState {
+    pub(crate) blend_mode: BlendMode,
+    pub(crate) clip_stack: Vec<Rect>,
+    pub(crate) camera_stack: Vec<Camera2D>,
+}
+
+struct DrawCommand<'frame> {
+    layer: i32,
+    z: f32,
+    state: DrawState,
+    draw: DrawFn<'frame>,
+}
+
+// Renderer handle inside Renderer::deferred; draw calls submitted through it may borrow
+// anything that outlives the frame, such as images held by an AssetManager. Queue lives only as
+// long as the deferred call rather than until present, because a queue kept on Renderer could
+// not hold such borrows; systems that must sort against each other share one frame
+pub struct DeferredFrame<'renderer, 'frame> {
+    renderer: &'renderer mut Renderer,
+    commands: Vec<DrawCommand<'frame>>,
+}
+
+impl<'renderer, 'frame> DeferredFrame<'renderer, 'frame> {
+    pub(crate) fn new(renderer: &'renderer mut Renderer) -> Self {
+        Self {
+            renderer,
+            commands: Vec::new(),
+        }
+    }
+
+    // Queues draw calls on a layer at depth z, under current blend mode, clips and cameras.
+    // While a canvas is targeted draws run right away instead, ignoring layer and z
+    pub fn submit(&mut self, layer: i32, z: f32, draw: impl FnOnce(&mut Renderer) + 'frame) {
+        // Canvas contents are usually needed right away, so only screen drawing is deferred
+        if self.renderer.is_drawing_to_canvas() {
+            draw(self.renderer);
+            return;
+        }
+
+        self.commands.push(DrawCommand {
+            layer,
+            z,
+            state: self.renderer.draw_state(),
+            draw: Box::new(draw),
+        });
+    }
+
+    // Returns number of draws waiting for frame to end
+    pub fn queued_draw_count(&self) -> usize {
+        self.commands.len()
+    }
+
+    // Runs queued draws sorted by layer, then z, then submission order; called when deferred
+    // closure returns, not at present
+    pub(crate) fn flush(mut self) {
+        // Stable sort keeps submission order for equal layer and z
+        self.commands.sort_by(|left, right| {
+            left.layer
+                .cmp(&right.layer)
+                .then(left.z.total_cmp(&right.z))
+        });
+
+        for command in self.commands {
+            let previous = self.renderer.replace_draw_state(command.state);
+            (command.draw)(self.renderer);
+            self.renderer.replace_draw_state(previous);
+        }
+    }
+}
+
+impl Deref for DeferredFrame<'_, '_> {
+    type Target = Renderer;
+
+    fn deref(&self) -> &Renderer {
+        self.renderer
+    }
+}
+
+impl DerefMut for DeferredFrame<'_, '_> {
+    fn deref_mut(&mut self) -> &mut Renderer {
+        self.renderer
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{assets::ImageData, renderer::Canvas};
+
+    #[test]
+    fn queued_draws_borrow_images_and_run_sorted() {
+        let red = ImageData::from_bytes(vec![255, 0, 0, 255], 1, 1);
+        let blue = ImageData::from_bytes(vec![0, 0, 255, 255], 1, 1);
+        let mut renderer = Renderer::offscreen(2, 1);
+
+        renderer.deferred(|frame| {
+            frame.submit(1, 0.0, |renderer| renderer.draw_image(&blue, 0, 0, 1));
+            frame.submit(0, 0.0, |renderer| renderer.draw_image(&red, 0, 0, 1));
+            frame.submit(0, 1.0, |renderer| renderer.draw_image(&blue, 1, 0, 1));
+            frame.submit(0, 0.5, |renderer| renderer.draw_image(&red, 1, 0, 1));
+
+            assert_eq!(frame.queued_draw_count(), 4);
+            assert_eq!(frame.pixel(0, 0), Some([0, 0, 0, 0]));
+        });
+
+        assert_eq!(renderer.pixel(0, 0), Some([0, 0, 255, 255]));
+        assert_eq!(renderer.pixel(1, 0), Some([0, 0, 255, 255]));
+    }
+
+    #[test]
+    fn queued_draws_keep_clip_they_were_submitted_under() {
+        let mut renderer = Renderer::offscreen(4, 1);
+
+        renderer.deferred(|frame| {
+            frame.push_clip(Rect::new(0, 0, 1, 1));
+            frame.submit(0, 0.0, |renderer| {
+                renderer.draw_rectangle(0, 0, 4, 1, [255, 255, 255, 255])
+            });
+            frame.pop_clip();
+        });
+
+        assert_eq!(renderer.pixel(0, 0), Some([255, 255, 255, 255]));
+        assert_eq!(renderer.pixel(1, 0), Some([0, 0, 0, 0]));
+    }
+
+    #[test]
+    fn draws_into_canvas_run_right_away_ignoring_layer_and_z() {
+        let mut renderer = Renderer::offscreen(1, 1);
+        let mut canvas = None;
+
+        renderer.deferred(|frame| {
+            frame.push_target(Canvas::new(1, 1));
+            frame.submit(1, 0.0, |renderer| {
+                renderer.draw_rectangle(0, 0, 1, 1, [0, 0, 255, 255])
+            });
+            frame.submit(0, 0.0, |renderer| {
+                renderer.draw_rectangle(0, 0, 1, 1, [255, 0, 0, 255])
+            });
+
+            assert_eq!(frame.queued_draw_count(), 0);
+            canvas = frame.pop_target();
+        });
+
+        assert_eq!(canvas.unwrap().pixel(0, 0), Some([255, 0, 0, 255]));
+        assert_eq!(renderer.pixel(0, 0), Some([0, 0, 0, 0]));
+    }
+}
@@ -0,0 +1,82 @@
+use super::{blend, BlendMode};
+use std::ops::Range;
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+// Smallest number of pixels worth splitting across threads
+#[cfg(feature = "parallel")]
+const PARALLEL_THRESHOLD: usize = 1 << 15;
+
+#[cfg(all(test, feature = "parallel"))]
+thread_local! {
+    // Keeps rows on calling thread, so tests can compare against parallel output
+    static FORCE_SERIAL: std::cell::Cell<bool> = const { std::cell::Cell::new(false) };
+}
+
+// Runs a kernel over each buffer row in range, across threads for large areas with parallel feature
+pub(crate) fn for_each_row<F>(
+    buffer: &mut [u32],
+    width: u32,
+    rows: Range<i32>,
+    columns: u32,
+    kernel: F,
+) where
+    F: Fn(i32, &mut [u32]) + Sync,
+{
+    if rows.is_empty() || width == 0 {
+        return;
+    }
+
+    let width = width as usize;
+    let first_row = rows.start;
+    let area = &mut buffer[rows.start as usize * width..rows.end as usize * width];
+
+    #[cfg(all(test, feature = "parallel"))]
+    let columns = if FORCE_SERIAL.get() { 0 } else { columns };
+
+    #[cfg(feature = "parallel")]
+    if rows.len() * columns as usize >= PARALLEL_THRESHOLD {
+        area.par_chunks_exact_mut(width)
+            .enumerate()
+            .for_each(|(offset, row)| kernel(first_row + offset as i32, row));
+        return;
+    }
+
+    #[cfg(not(feature = "parallel"))]
+    let _ = columns;
+
+    for (offset, row) in area.chunks_exact_mut(width).enumerate() {
+        kernel(first_row + offset as i32, row);
+    }
+}
+
+// Runs given function with every row kernel on current thread
+#[cfg(test)]
+pub(crate) fn serially<R>(function: impl FnOnce() -> R) -> R {
+    #[cfg(feature = "parallel")]
+    {
+        FORCE_SERIAL.set(true);
+        let result = function();
+        FORCE_SERIAL.set(false);
+        result
+    }
+
+    #[cfg(not(feature = "parallel"))]
+    function()
+}
+
+// Blends a single color into every pixel of a span
+pub(crate) fn blend_fill(pixels: &mut [u32], blend_mode: BlendMode, color: [u8; 4]) {
+    let [r, g, b, a] = color;
+
+    match (blend_mode, a) {
+        (BlendMode::Replace, _) | (BlendMode::Alpha, 255) => pixels.fill(blend::pack(r, g, b, a)),
+        (_, 0) => {}
+        _ => {
+            for pixel in pixels {
+                *pixel = blend_mode.blend(*pixel, color);
+            }
+        }
+    }
+}
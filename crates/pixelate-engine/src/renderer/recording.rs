@@ -0,0 +1,395 @@
+use super::RendererError;
+use crate::{assets::ImageData, log_warn};
+use image::{
+    codecs::gif::{GifEncoder, Repeat},
+    Delay, Frame, ImageError, RgbaImage,
+};
+use std::{
+    fs::{create_dir_all, File},
+    io::{self, BufWriter},
+    path::{Path, PathBuf},
+    sync::mpsc::{sync_channel, Receiver, SyncSender},
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+// Seconds a frame is assumed to stay on screen when presented without a frame time
+pub(crate) const DEFAULT_FRAME_TIME: f32 = 1.0 / 60.0;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RecordingFormat {
+    // Single looping animated GIF file
+    #[default]
+    Gif,
+    // Directory of numbered PNG files, one per recorded frame
+    PngSequence,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct RecordingConfiguration {
+    pub format: RecordingFormat,
+    pub max_seconds: f32,
+    pub frame_skip: u32,
+    pub downscale: u32,
+    // Frames waiting for writer thread before new ones are dropped, capping memory use
+    pub max_buffered_frames: usize,
+}
+
+impl Default for RecordingConfiguration {
+    // Creates a configuration recording up to ten seconds of GIF at every other frame
+    fn default() -> Self {
+        Self {
+            format: RecordingFormat::Gif,
+            max_seconds: 10.0,
+            frame_skip: 1,
+            downscale: 1,
+            max_buffered_frames: 8,
+        }
+    }
+}
+
+// Presented frame on its way to writer thread
+struct CapturedFrame {
+    image: ImageData,
+    // Seconds of gameplay recorded when frame was presented
+    presented: f32,
+}
+
+// Running recording, streaming presented frames to a writer thread as they arrive
+pub(crate) struct Recorder {
+    path: PathBuf,
+    configuration: RecordingConfiguration,
+    sender: SyncSender<CapturedFrame>,
+    writer: JoinHandle<Result<(), ImageError>>,
+    recorded_seconds: f32,
+    presented_frames: u32,
+    dropped_frames: u32,
+}
+
+impl Recorder {
+    pub(crate) fn new(
+        path: PathBuf,
+        configuration: RecordingConfiguration,
+    ) -> Result<Self, io::Error> {
+        let (sender, receiver) = sync_channel(configuration.max_buffered_frames.max(1));
+        let (writer_path, format) = (path.clone(), configuration.format);
+
+        let writer = thread::Builder::new()
+            .name("pixelate-recording".to_string())
+            .spawn(move || match format {
+                RecordingFormat::Gif => write_gif(&writer_path, receiver),
+                RecordingFormat::PngSequence => write_png_sequence(&writer_path, receiver),
+            })?;
+
+        Ok(Self {
+            path,
+            configuration,
+            sender,
+            writer,
+            recorded_seconds: 0.0,
+            presented_frames: 0,
+            dropped_frames: 0,
+        })
+    }
+
+    // Counts a frame presented delta_time seconds after previous one, returning true if it
+    // should be captured; game time rather than wall-clock time drives recordings, so
+    // headless runs with a fixed delta record at their simulated speed
+    pub(crate) fn wants_frame(&mut self, delta_time: f32) -> bool {
+        self.recorded_seconds += delta_time.max(0.0);
+
+        let keep = self.presented_frames % self.configuration.frame_skip.saturating_add(1) == 0;
+        self.presented_frames = self.presented_frames.wrapping_add(1);
+
+        keep
+    }
+
+    // Hands a frame to writer thread, dropping it if writer has fallen too far behind
+    pub(crate) fn capture(&mut self, rgba_bytes: Vec<u8>, width: u32, height: u32) {
+        let frame = CapturedFrame {
+            image: downscale(rgba_bytes, width, height, self.configuration.downscale),
+            presented: self.recorded_seconds,
+        };
+
+        if self.sender.try_send(frame).is_err() {
+            self.dropped_frames += 1;
+        }
+    }
+
+    // Checks if recorded game time reached limit or writer thread stopped early on an error
+    pub(crate) fn is_done(&self) -> bool {
+        self.recorded_seconds >= self.configuration.max_seconds || self.writer.is_finished()
+    }
+
+    // Stops capturing; writer thread finishes buffered frames in background
+    pub(crate) fn stop(self) -> FinishingRecording {
+        if self.dropped_frames > 0 {
+            log_warn!(
+                "Renderer",
+                "Recording {} dropped {} frames while writer was busy",
+                self.path.display(),
+                self.dropped_frames
+            );
+        }
+
+        // Closing channel tells writer no more frames are coming
+        drop(self.sender);
+
+        FinishingRecording {
+            path: self.path,
+            writer: self.writer,
+        }
+    }
+}
+
+// Stopped recording whose writer thread may still be writing buffered frames
+pub(crate) struct FinishingRecording {
+    path: PathBuf,
+    writer: JoinHandle<Result<(), ImageError>>,
+}
+
+impl FinishingRecording {
+    // Returns path recording is written to
+    pub(crate) fn path(&self) -> &Path {
+        &self.path
+    }
+
+    // Checks if writer thread is done, so waiting will not block
+    pub(crate) fn is_finished(&self) -> bool {
+        self.writer.is_finished()
+    }
+
+    // Waits for writer thread and returns path written
+    pub(crate) fn wait(self) -> Result<PathBuf, RendererError> {
+        let result = self.writer.join().unwrap_or_else(|_| {
+            Err(ImageError::IoError(io::Error::other(
+                "recording writer thread panicked",
+            )))
+        });
+
+        match result {
+            Ok(()) => Ok(self.path),
+            Err(source) => Err(RendererError::RecordingFailed {
+                path: self.path.display().to_string(),
+                source,
+            }),
+        }
+    }
+}
+
+// Encodes frames into a looping GIF as they arrive; a frame is written once next one
+// shows how long it stayed on screen, and last frame repeats previous interval
+fn write_gif(path: &Path, receiver: Receiver<CapturedFrame>) -> Result<(), ImageError> {
+    if let Some(parent) = path.parent() {
+        create_dir_all(parent).map_err(ImageError::IoError)?;
+    }
+
+    let file = File::create(path).map_err(ImageError::IoError)?;
+    let mut encoder = GifEncoder::new(BufWriter::new(file));
+    encoder.set_repeat(Repeat::Infinite)?;
+
+    let mut pending: Option<CapturedFrame> = None;
+    let mut delay = Duration::from_millis(100);
+
+    for frame in receiver {
+        if let Some(previous) = pending.take() {
+            // Rounded to whole milliseconds so float error does not drop a GIF tick
+            let milliseconds = (frame.presented - previous.presented) * 1000.0;
+            delay = Duration::from_millis(milliseconds.round().max(0.0) as u64);
+            encode_gif_frame(&mut encoder, previous.image, delay)?;
+        }
+
+        pending = Some(frame);
+    }
+
+    if let Some(last) = pending {
+        encode_gif_frame(&mut encoder, last.image, delay)?;
+    }
+
+    Ok(())
+}
+
+fn encode_gif_frame(
+    encoder: &mut GifEncoder<BufWriter<File>>,
+    image: ImageData,
+    delay: Duration,
+) -> Result<(), ImageError> {
+    let Some(buffer) = RgbaImage::from_raw(image.width, image.height, image.rgba_bytes) else {
+        return Ok(());
+    };
+
+    encoder.encode_frame(Frame::from_parts(
+        buffer,
+        0,
+        0,
+        Delay::from_saturating_duration(delay),
+    ))
+}
+
+// Writes each frame as a numbered PNG as soon as it arrives
+fn write_png_sequence(path: &Path, receiver: Receiver<CapturedFrame>) -> Result<(), ImageError> {
+    create_dir_all(path).map_err(ImageError::IoError)?;
+
+    for (index, frame) in receiver.into_iter().enumerate() {
+        frame
+            .image
+            .save_png(path.join(format!("frame-{:05}.png", index)))?;
+    }
+
+    Ok(())
+}
+
+// Shrinks RGBA bytes by an integer factor, keeping top-left pixel of each block
+fn downscale(rgba_bytes: Vec<u8>, width: u32, height: u32, factor: u32) -> ImageData {
+    let factor = factor.max(1);
+
+    if factor == 1 {
+        return ImageData::from_bytes(rgba_bytes, width, height);
+    }
+
+    let (scaled_width, scaled_height) = ((width / factor).max(1), (height / factor).max(1));
+    let mut scaled = Vec::with_capacity((scaled_width * scaled_height * 4) as usize);
+
+    for y in 0..scaled_height {
+        for x in 0..scaled_width {
+            let index = (((y * factor) * width + x * factor) * 4) as usize;
+            scaled.extend_from_slice(&rgba_bytes[index..index + 4]);
+        }
+    }
+
+    ImageData::from_bytes(scaled, scaled_width, scaled_height)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::renderer::Renderer;
+    use image::{codecs::gif::GifDecoder, AnimationDecoder};
+    use std::{env, fs};
+
+    fn scratch_path(name: &str) -> PathBuf {
+        let path = env::temp_dir().join(format!(
+            "pixelate-recording-{}-{}",
+            name,
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&path);
+        let _ = fs::remove_file(&path);
+        path
+    }
+
+    fn record(path: &Path, configuration: RecordingConfiguration, frames: u8) -> Vec<PathBuf> {
+        record_timed(path, configuration, frames, DEFAULT_FRAME_TIME)
+    }
+
+    fn record_timed(
+        path: &Path,
+        configuration: RecordingConfiguration,
+        frames: u8,
+        delta_time: f32,
+    ) -> Vec<PathBuf> {
+        let mut renderer = Renderer::offscreen(8, 4);
+        renderer.start_recording(path, configuration).unwrap();
+
+        for frame in 0..frames {
+            renderer.clear([frame * 20, 0, 0, 255]);
+            renderer.present_timed(delta_time).unwrap();
+        }
+
+        renderer.finish_recordings().unwrap()
+    }
+
+    #[test]
+    fn gif_is_streamed_with_skipped_and_downscaled_frames() {
+        let path = scratch_path("gif").with_extension("gif");
+        let configuration = RecordingConfiguration {
+            frame_skip: 1,
+            downscale: 2,
+            ..Default::default()
+        };
+
+        assert_eq!(record(&path, configuration, 6), vec![path.clone()]);
+
+        let decoder = GifDecoder::new(io::BufReader::new(File::open(&path).unwrap())).unwrap();
+        let frames = decoder.into_frames().collect_frames().unwrap();
+
+        assert_eq!(frames.len(), 3);
+        assert_eq!(frames[0].buffer().dimensions(), (4, 2));
+
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn png_sequence_writes_numbered_frames() {
+        let path = scratch_path("png");
+        let configuration = RecordingConfiguration {
+            format: RecordingFormat::PngSequence,
+            frame_skip: 0,
+            ..Default::default()
+        };
+
+        record(&path, configuration, 3);
+
+        assert!(path.join("frame-00002.png").exists());
+        assert!(!path.join("frame-00003.png").exists());
+
+        let _ = fs::remove_dir_all(&path);
+    }
+
+    #[test]
+    fn largest_frame_skip_keeps_only_first_frame() {
+        let path = scratch_path("skip");
+        let configuration = RecordingConfiguration {
+            format: RecordingFormat::PngSequence,
+            frame_skip: u32::MAX,
+            ..Default::default()
+        };
+
+        record(&path, configuration, 3);
+
+        assert!(path.join("frame-00000.png").exists());
+        assert!(!path.join("frame-00001.png").exists());
+
+        let _ = fs::remove_dir_all(&path);
+    }
+
+    #[test]
+    fn stopping_does_not_wait_for_writer() {
+        let path = scratch_path("stop").with_extension("gif");
+        let mut renderer = Renderer::offscreen(4, 4);
+
+        renderer
+            .start_recording(&path, RecordingConfiguration::default())
+            .unwrap();
+        renderer.present().unwrap();
+
+        assert_eq!(renderer.stop_recording(), Some(path.clone()));
+        assert!(!renderer.is_recording());
+        assert_eq!(renderer.finish_recordings().unwrap(), vec![path.clone()]);
+
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn gif_delays_and_length_follow_frame_time() {
+        let path = scratch_path("timed").with_extension("gif");
+        let configuration = RecordingConfiguration {
+            max_seconds: 0.2,
+            frame_skip: 0,
+            ..Default::default()
+        };
+
+        record_timed(&path, configuration, 10, 0.05);
+
+        let decoder = GifDecoder::new(io::BufReader::new(File::open(&path).unwrap())).unwrap();
+        let frames = decoder.into_frames().collect_frames().unwrap();
+
+        assert_eq!(frames.len(), 4);
+
+        for frame in &frames {
+            assert_eq!(frame.delay().numer_denom_ms(), (50, 1));
+        }
+
+        let _ = fs::remove_file(&path);
+    }
+}
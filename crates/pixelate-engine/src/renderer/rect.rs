@@ -0,0 +1,79 @@
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Rect {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl Rect {
+    // Creates a rectangle from top-left corner and size
+    pub const fn new(x: i32, y: i32, width: u32, height: u32) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    // Returns x coordinate one past right edge
+    pub fn right(&self) -> i32 {
+        self.x + self.width as i32
+    }
+
+    // Returns y coordinate one past bottom edge
+    pub fn bottom(&self) -> i32 {
+        self.y + self.height as i32
+    }
+
+    // Checks if rectangle covers no pixels
+    pub fn is_empty(&self) -> bool {
+        self.width == 0 || self.height == 0
+    }
+
+    // Checks if a point lies inside rectangle
+    pub fn contains(&self, x: i32, y: i32) -> bool {
+        x >= self.x && y >= self.y && x < self.right() && y < self.bottom()
+    }
+
+    // Returns overlapping area of two rectangles, if any
+    pub fn intersection(&self, other: &Rect) -> Option<Rect> {
+        let left = self.x.max(other.x);
+        let top = self.y.max(other.y);
+        let right = self.right().min(other.right());
+        let bottom = self.bottom().min(other.bottom());
+
+        if left >= right || top >= bottom {
+            return None;
+        }
+
+        Some(Rect::new(
+            left,
+            top,
+            (right - left) as u32,
+            (bottom - top) as u32,
+        ))
+    }
+
+    // Returns smallest rectangle covering both rectangles, ignoring empty ones
+    pub fn union(&self, other: &Rect) -> Rect {
+        if self.is_empty() {
+            return *other;
+        }
+
+        if other.is_empty() {
+            return *self;
+        }
+
+        let left = self.x.min(other.x);
+        let top = self.y.min(other.y);
+
+        Rect::new(
+            left,
+            top,
+            (self.right().max(other.right()) - left) as u32,
+            (self.bottom().max(other.bottom()) - top) as u32,
+        )
+    }
+}
@@ -1,20 +1,54 @@
-use super::RendererError;
-use crate::{assets::ImageData, log_debug, log_info, window::Window};
-use softbuffer::{Context, Surface};
-use std::{num::NonZeroU32, sync::Arc};
+use super::{
+    blend,
+    camera::View,
+    canvas::PixelSource,
+    damage::DamageTracker,
+    queue::{DeferredFrame, DrawState},
+    raster,
+    recording::{FinishingRecording, Recorder, DEFAULT_FRAME_TIME},
+    BlendMode, Camera2D, Canvas, DrawParams, LineCap, LineStyle, PostEffect,
+    RecordingConfiguration, Rect, RendererError,
+};
+use crate::{
+    assets::{BitmapFont, Font, ImageData, SpriteSheet, TextAlign},
+    input::Input,
+    log_debug, log_error, log_info, log_warn,
+    window::Window,
+};
+use softbuffer::{Context, Rect as SurfaceRect, Surface};
+use std::{mem, num::NonZeroU32, path::PathBuf, sync::Arc};
 use winit::window::Window as WinitWindow;
 
 pub struct Renderer {
-    surface: Surface<Arc<WinitWindow>, Arc<WinitWindow>>,
+    surface: Option<Surface<Arc<WinitWindow>, Arc<WinitWindow>>>,
     buffer: Vec<u32>,
     width: u32,
     height: u32,
+    surface_width: u32,
+    surface_height: u32,
+    logical_size: Option<(u32, u32)>,
+    border_color: [u8; 4],
+    blend_mode: BlendMode,
+    target_stack: Vec<(Canvas, Vec<Rect>)>,
+    clip_stack: Vec<Rect>,
+    camera_stack: Vec<Camera2D>,
+    recorder: Option<Recorder>,
+    finishing_recordings: Vec<FinishingRecording>,
+    post_effects: Vec<Box<dyn PostEffect>>,
+    post_processing_enabled: bool,
+    post_buffer: Vec<u32>,
+    post_output_stale: bool,
+    damage: DamageTracker,
 }
 
 impl Renderer {
     // Creates a new renderer for given window
     pub fn new(window: &Window) -> Result<Self, RendererError> {
-        let inner_window = window.inner();
+        let Some(inner_window) = window.try_inner() else {
+            let (width, height) = window.inner_size();
+
+            return Ok(Self::offscreen(width, height));
+        };
 
         let context = Context::new(inner_window.clone())
             .map_err(|source| RendererError::ContextCreationFailed { source })?;
@@ -24,223 +58,1880 @@ impl Renderer {
 
         let (width, height) = window.inner_size();
 
+        let mut renderer = Self::offscreen(width, height);
+        renderer.surface = Some(surface);
+        renderer.resize_surface(width, height)?;
+
         log_info!("Renderer", "Created renderer ({}x{})", width, height);
 
-        Ok(Self {
-            surface,
+        Ok(renderer)
+    }
+
+    // Creates a renderer without a window surface, drawing into memory only
+    pub fn offscreen(width: u32, height: u32) -> Self {
+        log_info!(
+            "Renderer",
+            "Created offscreen renderer ({}x{})",
+            width,
+            height
+        );
+
+        Self {
+            surface: None,
             buffer: vec![0; (width * height) as usize],
             width,
             height,
-        })
+            surface_width: width,
+            surface_height: height,
+            logical_size: None,
+            border_color: [0, 0, 0, 255],
+            blend_mode: BlendMode::default(),
+            target_stack: Vec::new(),
+            clip_stack: Vec::new(),
+            camera_stack: Vec::new(),
+            recorder: None,
+            finishing_recordings: Vec::new(),
+            post_effects: Vec::new(),
+            post_processing_enabled: true,
+            post_buffer: Vec::new(),
+            post_output_stale: true,
+            damage: DamageTracker::new(),
+        }
+    }
+
+    // Converts screen buffer to opaque RGBA bytes as it appears on screen
+    pub(crate) fn to_rgba_bytes(&self) -> Vec<u8> {
+        let (buffer, _, _) = self.screen_output();
+        let mut rgba_bytes = Vec::with_capacity(buffer.len() * 4);
+
+        for &pixel in buffer {
+            let [r, g, b, _a] = blend::unpack(pixel);
+            rgba_bytes.extend_from_slice(&[r, g, b, 255]);
+        }
+
+        rgba_bytes
+    }
+
+    // Returns a copy of current frame as an opaque image, even while a canvas is targeted
+    pub fn screenshot(&self) -> ImageData {
+        let (_, width, height) = self.screen_buffer();
+
+        ImageData::from_bytes(self.to_rgba_bytes(), width, height)
+    }
+
+    // Starts streaming presented frames to path, written on a background thread
+    pub fn start_recording(
+        &mut self,
+        path: impl Into<PathBuf>,
+        configuration: RecordingConfiguration,
+    ) -> Result<(), RendererError> {
+        if self.recorder.is_some() {
+            log_warn!(
+                "Renderer",
+                "Recording already running, ignoring start request"
+            );
+            return Ok(());
+        }
+
+        let path = path.into();
+        let recorder = Recorder::new(path.clone(), configuration).map_err(|source| {
+            RendererError::RecordingStartFailed {
+                path: path.display().to_string(),
+                source,
+            }
+        })?;
+
+        log_info!("Renderer", "Started recording into {}", path.display());
+        self.recorder = Some(recorder);
+
+        Ok(())
+    }
+
+    // Stops recording without waiting for buffered frames to be written, returning its path
+    pub fn stop_recording(&mut self) -> Option<PathBuf> {
+        let finishing = self.recorder.take()?.stop();
+        let path = finishing.path().to_path_buf();
+
+        log_info!("Renderer", "Stopped recording into {}", path.display());
+        self.finishing_recordings.push(finishing);
+
+        Some(path)
     }
 
-    // Resizes render buffer
+    // Stops any running recording and waits until every recording is fully written
+    pub fn finish_recordings(&mut self) -> Result<Vec<PathBuf>, RendererError> {
+        self.stop_recording();
+
+        let mut paths = Vec::new();
+        let mut first_error = None;
+
+        for finishing in self.finishing_recordings.drain(..) {
+            match finishing.wait() {
+                Ok(path) => {
+                    log_info!("Renderer", "Saved recording to {}", path.display());
+                    paths.push(path);
+                }
+                Err(error) if first_error.is_none() => first_error = Some(error),
+                Err(error) => log_error!("Renderer", "{}", error),
+            }
+        }
+
+        match first_error {
+            Some(error) => Err(error),
+            None => Ok(paths),
+        }
+    }
+
+    // Checks if presented frames are being recorded
+    pub fn is_recording(&self) -> bool {
+        self.recorder.is_some()
+    }
+
+    // Checks if renderer has no window surface to present to
+    pub fn is_offscreen(&self) -> bool {
+        self.surface.is_none()
+    }
+
+    // Returns width of current draw target in pixels
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    // Returns height of current draw target in pixels
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    // Returns current draw target as packed ARGB pixels in row-major order
+    pub fn pixels(&self) -> &[u32] {
+        &self.buffer
+    }
+
+    // Returns RGBA color of pixel at specified coordinates
+    pub fn pixel(&self, x: i32, y: i32) -> Option<[u8; 4]> {
+        if x < 0 || y < 0 || x >= self.width as i32 || y >= self.height as i32 {
+            return None;
+        }
+
+        let buffer_index = (y as u32 * self.width + x as u32) as usize;
+
+        Some(blend::unpack(self.buffer[buffer_index]))
+    }
+
+    // Resizes window surface; render buffer follows unless a logical size is set
     pub fn resize(&mut self, width: u32, height: u32) -> Result<(), RendererError> {
-        if self.width == width && self.height == height {
+        if self.surface_width == width && self.surface_height == height {
             return Ok(());
         }
 
         log_debug!(
             "Renderer",
-            "Resizing from {}x{} to {}x{}",
-            self.width,
-            self.height,
+            "Resizing surface from {}x{} to {}x{}",
+            self.surface_width,
+            self.surface_height,
             width,
             height
         );
 
-        self.width = width;
-        self.height = height;
-        self.buffer.resize((width * height) as usize, 0);
+        // Minimized windows report a zero size; keep last real size until window is restored
+        if self.surface.is_some() && (width == 0 || height == 0) {
+            return Ok(());
+        }
 
-        if let (Some(width), Some(height)) = (NonZeroU32::new(width), NonZeroU32::new(height)) {
-            self.surface
-                .resize(width, height)
-                .map_err(|source| RendererError::ResizeFailed {
-                    width: width.get(),
-                    height: height.get(),
-                    source,
-                })?;
+        if self.logical_size.is_none() {
+            self.resize_buffer(width, height);
         }
 
-        Ok(())
+        self.resize_surface(width, height)
     }
 
-    // Clears buffer with a solid color
-    pub fn clear(&mut self, color: [u8; 4]) {
-        let color_u32 = Self::rgba_to_u32(color);
+    // Returns fixed logical resolution, if one is set
+    pub fn logical_size(&self) -> Option<(u32, u32)> {
+        self.logical_size
+    }
+
+    // Sets fixed logical resolution that is upscaled to surface, or None to draw at surface size
+    pub fn set_logical_size(&mut self, logical_size: Option<(u32, u32)>) {
+        self.logical_size = logical_size;
+
+        let (width, height) = logical_size.unwrap_or((self.surface_width, self.surface_height));
 
-        self.buffer.fill(color_u32);
+        log_debug!("Renderer", "Render buffer set to {}x{}", width, height);
+
+        self.resize_buffer(width, height);
     }
 
-    // Draws an image at specified position with scaling
-    pub fn draw_image(&mut self, image: &ImageData, x: i32, y: i32, size: u32) {
-        let image_rgba_bytes = &image.rgba_bytes;
-        let image_width = image.width;
-        let image_height = image.height;
-
-        for destination_y in 0..size {
-            for destination_x in 0..size {
-                let screen_x = x + destination_x as i32;
-                let screen_y = y + destination_y as i32;
-
-                if screen_x < 0
-                    || screen_y < 0
-                    || screen_x >= self.width as i32
-                    || screen_y >= self.height as i32
-                {
-                    continue;
-                }
+    // Sets color of letterbox bars around upscaled logical frame
+    pub fn set_border_color(&mut self, border_color: [u8; 4]) {
+        self.border_color = border_color;
+        self.damage.add_all();
+    }
 
-                let source_x = (destination_x * image_width) / size;
-                let source_y = (destination_y * image_height) / size;
-                let source_index = ((source_y * image_width + source_x) * 4) as usize;
+    // Returns top-left offset and integer scale of render buffer within surface
+    pub fn viewport(&self) -> (i32, i32, u32) {
+        if self.logical_size.is_none() {
+            return (0, 0, 1);
+        }
 
-                let alpha = image_rgba_bytes[source_index + 3];
-                if alpha == 0 {
-                    continue;
-                }
+        // Surfaces smaller than logical size are cropped rather than scaled down
+        let scale = (self.surface_width / self.width.max(1))
+            .min(self.surface_height / self.height.max(1))
+            .max(1);
+
+        let offset_x = (self.surface_width as i32 - (self.width * scale) as i32) / 2;
+        let offset_y = (self.surface_height as i32 - (self.height * scale) as i32) / 2;
 
-                let rgba = [
-                    image_rgba_bytes[source_index],
-                    image_rgba_bytes[source_index + 1],
-                    image_rgba_bytes[source_index + 2],
-                    alpha,
-                ];
+        (offset_x, offset_y, scale)
+    }
 
-                let buffer_index = (screen_y as u32 * self.width + screen_x as u32) as usize;
+    // Maps surface coordinates, such as cursor position, into render buffer coordinates
+    pub fn to_logical(&self, x: f32, y: f32) -> (f32, f32) {
+        let (offset_x, offset_y, scale) = self.viewport();
 
-                self.buffer[buffer_index] = Self::rgba_to_u32(rgba);
-            }
+        (
+            (x - offset_x as f32) / scale as f32,
+            (y - offset_y as f32) / scale as f32,
+        )
+    }
+
+    // Returns blend mode used by draw calls
+    pub fn blend_mode(&self) -> BlendMode {
+        self.blend_mode
+    }
+
+    // Sets blend mode used by subsequent draw calls
+    pub fn set_blend_mode(&mut self, blend_mode: BlendMode) {
+        self.blend_mode = blend_mode;
+    }
+
+    // Redirects all drawing into canvas until matching pop_target; canvas starts unclipped
+    pub fn push_target(&mut self, mut canvas: Canvas) {
+        mem::swap(&mut self.buffer, &mut canvas.buffer);
+        mem::swap(&mut self.width, &mut canvas.width);
+        mem::swap(&mut self.height, &mut canvas.height);
+
+        let clip_stack = mem::take(&mut self.clip_stack);
+        self.target_stack.push((canvas, clip_stack));
+    }
+
+    // Restores previous draw target with its clips and returns canvas that was being drawn into
+    pub fn pop_target(&mut self) -> Option<Canvas> {
+        let (mut canvas, clip_stack) = self.target_stack.pop()?;
+
+        mem::swap(&mut self.buffer, &mut canvas.buffer);
+        mem::swap(&mut self.width, &mut canvas.width);
+        mem::swap(&mut self.height, &mut canvas.height);
+
+        self.clip_stack = clip_stack;
+
+        Some(canvas)
+    }
+
+    // Runs draw calls with canvas as draw target, restoring previous target afterwards
+    pub fn with_target(&mut self, canvas: &mut Canvas, draw: impl FnOnce(&mut Renderer)) {
+        let depth = self.target_stack.len();
+
+        self.push_target(mem::replace(canvas, Canvas::new(0, 0)));
+        draw(self);
+
+        // Targets left pushed inside closure are unwound so canvas always comes back
+        while self.target_stack.len() > depth + 1 {
+            self.pop_target();
+        }
+
+        if let Some(drawn) = self.pop_target() {
+            *canvas = drawn;
         }
     }
 
-    // Draws a filled rectangle
-    pub fn draw_rectangle(&mut self, x: i32, y: i32, width: u32, height: u32, color: [u8; 4]) {
-        let color_u32 = Self::rgba_to_u32(color);
-
-        for offset_y in 0..height {
-            for offset_x in 0..width {
-                let screen_x = x + offset_x as i32;
-                let screen_y = y + offset_y as i32;
-
-                if screen_x < 0
-                    || screen_y < 0
-                    || screen_x >= self.width as i32
-                    || screen_y >= self.height as i32
-                {
-                    continue;
-                }
+    // Checks if drawing currently goes into an offscreen canvas
+    pub fn is_drawing_to_canvas(&self) -> bool {
+        !self.target_stack.is_empty()
+    }
 
-                let buffer_index = (screen_y as u32 * self.width + screen_x as u32) as usize;
+    // Restricts drawing to rect, intersected with current clip, until matching pop_clip
+    pub fn push_clip(&mut self, rect: Rect) {
+        let clip = self
+            .clip_bounds()
+            .intersection(&rect)
+            .unwrap_or(Rect::new(rect.x, rect.y, 0, 0));
 
-                self.buffer[buffer_index] = color_u32;
-            }
+        self.clip_stack.push(clip);
+    }
+
+    // Restores previous clip and returns the one removed
+    pub fn pop_clip(&mut self) -> Option<Rect> {
+        self.clip_stack.pop()
+    }
+
+    // Returns area of current draw target that draw calls may change, in screen pixels
+    pub fn clip_bounds(&self) -> Rect {
+        let target = Rect::new(0, 0, self.width, self.height);
+
+        match self.clip_stack.last() {
+            Some(clip) => clip
+                .intersection(&target)
+                .unwrap_or(Rect::new(clip.x, clip.y, 0, 0)),
+            None => target,
         }
     }
 
-    // Draws a line between two points
-    pub fn draw_line(&mut self, x1: i32, y1: i32, x2: i32, y2: i32, color: [u8; 4]) {
-        let delta_x = (x2 - x1).abs();
-        let delta_y = -(y2 - y1).abs();
-        let step_x = if x1 < x2 { 1 } else { -1 };
-        let step_y = if y1 < y2 { 1 } else { -1 };
-        let mut error = delta_x + delta_y;
+    // Makes draw calls use world coordinates seen through camera until matching pop_camera
+    pub fn push_camera(&mut self, camera: Camera2D) {
+        self.camera_stack.push(camera);
+    }
 
-        let mut current_x = x1;
-        let mut current_y = y1;
+    // Restores previous camera, or plain screen coordinates once none is left
+    pub fn pop_camera(&mut self) -> Option<Camera2D> {
+        self.camera_stack.pop()
+    }
 
-        loop {
-            self.draw_point(current_x, current_y, color);
+    // Returns camera currently applied to draw calls
+    pub fn camera(&self) -> Option<&Camera2D> {
+        self.camera_stack.last()
+    }
 
-            if current_x == x2 && current_y == y2 {
-                break;
+    // Converts a position on current draw target to world coordinates of active camera
+    pub fn screen_to_world(&self, x: f32, y: f32) -> (f32, f32) {
+        self.view().map_or((x, y), |view| view.to_world(x, y))
+    }
+
+    // Converts world coordinates of active camera to a position on current draw target
+    pub fn world_to_screen(&self, x: f32, y: f32) -> (f32, f32) {
+        self.view().map_or((x, y), |view| view.to_screen(x, y))
+    }
+
+    // Returns mouse position in world coordinates of active camera
+    pub fn mouse_world_position(&self, input: &Input) -> (f32, f32) {
+        let (x, y) = input.mouse_position();
+
+        self.screen_to_world(x, y)
+    }
+
+    // Runs closure with a frame whose submitted draws run sorted by layer, then z, then
+    // submission order once closure returns, so draws from separate systems only sort against
+    // each other when submitted to same frame, e.g. on_render passing one frame to each system.
+    // Draws made directly still happen right away
+    pub fn deferred<'frame>(&mut self, record: impl FnOnce(&mut DeferredFrame<'_, 'frame>)) {
+        let mut frame = DeferredFrame::new(self);
+        record(&mut frame);
+        frame.flush();
+    }
+
+    // Appends an effect to post-process chain, run in order of addition
+    pub fn add_post_effect(&mut self, effect: impl PostEffect + 'static) {
+        self.post_effects.push(Box::new(effect));
+        self.invalidate_post_output();
+    }
+
+    // Removes most recently added post-process effect
+    pub fn pop_post_effect(&mut self) -> Option<Box<dyn PostEffect>> {
+        let effect = self.post_effects.pop();
+
+        if effect.is_some() {
+            self.invalidate_post_output();
+        }
+
+        effect
+    }
+
+    // Removes all post-process effects
+    pub fn clear_post_effects(&mut self) {
+        if !self.post_effects.is_empty() {
+            self.post_effects.clear();
+            self.invalidate_post_output();
+        }
+    }
+
+    // Returns number of effects in post-process chain
+    pub fn post_effect_count(&self) -> usize {
+        self.post_effects.len()
+    }
+
+    // Turns post-processing on or off without discarding effects
+    pub fn set_post_processing_enabled(&mut self, enabled: bool) {
+        if self.post_processing_enabled != enabled {
+            self.post_processing_enabled = enabled;
+            self.invalidate_post_output();
+        }
+    }
+
+    // Checks if post-process effects run on present
+    pub fn is_post_processing_enabled(&self) -> bool {
+        self.post_processing_enabled
+    }
+
+    // Runs post-process chain over finished frame into a separate output buffer that present,
+    // screenshots and recordings use; drawn pixels are left untouched. Present calls this itself
+    pub fn apply_post_effects(&mut self) {
+        if !self.post_effects_active() || self.is_drawing_to_canvas() || self.buffer.is_empty() {
+            return;
+        }
+
+        let regions = match self.damage.current() {
+            _ if self.post_output_stale || self.post_buffer.len() != self.buffer.len() => None,
+            regions => regions.map(<[Rect]>::to_vec),
+        };
+
+        match regions {
+            // Nothing was drawn, so last output still matches frame
+            Some(regions) if regions.is_empty() => {}
+            Some(regions) if self.post_effects.iter().all(|effect| effect.is_per_pixel()) => {
+                for region in regions {
+                    self.post_process_region(region);
+                }
             }
+            _ => {
+                self.post_buffer.clear();
+                self.post_buffer.extend_from_slice(&self.buffer);
 
-            let error_doubled = 2 * error;
-            if error_doubled >= delta_y {
-                if current_x == x2 {
-                    break;
+                for effect in &mut self.post_effects {
+                    effect.apply(&mut self.post_buffer, self.width, self.height);
                 }
 
-                error += delta_y;
-                current_x += step_x;
+                self.post_output_stale = false;
+
+                // Effects may move pixels around, so a changed frame can differ anywhere on output
+                self.damage.add_all();
             }
+        }
+    }
 
-            if error_doubled <= delta_x {
-                if current_y == y2 {
-                    break;
-                }
+    // Checks if post-process chain has effects to run
+    fn post_effects_active(&self) -> bool {
+        self.post_processing_enabled && !self.post_effects.is_empty()
+    }
 
-                error += delta_x;
-                current_y += step_y;
+    // Checks if post-process output holds what screen should show
+    fn shows_post_output(&self) -> bool {
+        self.post_effects_active()
+            && !self.post_output_stale
+            && self.post_buffer.len() == self.screen_buffer().0.len()
+    }
+
+    // Marks post-process output outdated after effect chain changed; screen changes everywhere
+    fn invalidate_post_output(&mut self) {
+        self.post_output_stale = true;
+        self.damage.add_all();
+    }
+
+    // Reprocesses a region of output pixel by pixel; only valid when every effect is per-pixel
+    fn post_process_region(&mut self, region: Rect) {
+        let Some(region) = region.intersection(&Rect::new(0, 0, self.width, self.height)) else {
+            return;
+        };
+
+        for y in region.y..region.bottom() {
+            let row_start = (y as u32 * self.width) as usize;
+
+            for x in region.x..region.right() {
+                let index = row_start + x as usize;
+                let [r, g, b, a] = self
+                    .post_effects
+                    .iter()
+                    .fold(blend::unpack(self.buffer[index]), |color, effect| {
+                        effect.process_pixel(x as u32, y as u32, color)
+                    });
+
+                self.post_buffer[index] = blend::pack(r, g, b, a);
             }
         }
     }
 
-    // Draws a circle outline using midpoint circle
-    pub fn draw_circle(&mut self, center_x: i32, center_y: i32, radius: u32, color: [u8; 4]) {
-        if radius == 0 {
-            self.draw_point(center_x, center_y, color);
+    // Returns areas of screen changed since last present, merged into a few rectangles
+    pub fn damaged_regions(&self) -> Vec<Rect> {
+        match self.damage.current() {
+            Some(regions) => regions.to_vec(),
+            None => vec![Rect::new(0, 0, self.width, self.height)],
+        }
+    }
+
+    // Marks whole screen as changed so next present copies every pixel
+    pub fn invalidate(&mut self) {
+        self.damage.add_all();
+    }
+
+    // Clears buffer with a solid color
+    pub fn clear(&mut self, color: [u8; 4]) {
+        let [r, g, b, a] = color;
+        let pixel = blend::pack(r, g, b, a);
+        let clip = self.clip_bounds();
+
+        self.mark_damaged(clip);
+
+        if self.clip_stack.is_empty() {
+            self.buffer.fill(pixel);
             return;
         }
 
-        let mut offset_x = 0i32;
-        let mut offset_y = radius as i32;
-        let mut decision_parameter = 1 - radius as i32;
+        for y in clip.y..clip.bottom() {
+            let row_start = (y as u32 * self.width) as usize;
 
-        while offset_x <= offset_y {
-            self.draw_point(center_x + offset_x, center_y + offset_y, color);
-            self.draw_point(center_x - offset_x, center_y + offset_y, color);
-            self.draw_point(center_x + offset_x, center_y - offset_y, color);
-            self.draw_point(center_x - offset_x, center_y - offset_y, color);
-            self.draw_point(center_x + offset_y, center_y + offset_x, color);
-            self.draw_point(center_x - offset_y, center_y + offset_x, color);
-            self.draw_point(center_x + offset_y, center_y - offset_x, color);
-            self.draw_point(center_x - offset_y, center_y - offset_x, color);
+            self.buffer[row_start + clip.x as usize..row_start + clip.right() as usize].fill(pixel);
+        }
+    }
 
-            offset_x += 1;
+    // Draws an image at specified position with scaling
+    pub fn draw_image(&mut self, image: &ImageData, x: i32, y: i32, size: u32) {
+        let source = Rect::new(0, 0, image.width, image.height);
 
-            if decision_parameter < 0 {
-                decision_parameter += 2 * offset_x + 1;
-            } else {
-                offset_y -= 1;
-                decision_parameter += 2 * (offset_x - offset_y) + 1;
-            }
+        self.draw_image_region(image, source, Rect::new(x, y, size, size));
+    }
+
+    // Draws a source rectangle of an image scaled into a destination rectangle
+    pub fn draw_image_region(&mut self, image: &ImageData, source: Rect, destination: Rect) {
+        self.draw_region_of(image, source, destination);
+    }
+
+    // Draws an image with flipping, rotation and scaling around a pivot at given position
+    pub fn draw_image_ex(&mut self, image: &ImageData, x: f32, y: f32, params: &DrawParams) {
+        self.draw_transformed_of(image, x, y, params);
+    }
+
+    // Draws a canvas at its own size with top-left at given position
+    pub fn draw_canvas(&mut self, canvas: &Canvas, x: i32, y: i32) {
+        let source = Rect::new(0, 0, canvas.width, canvas.height);
+
+        self.draw_region_of(canvas, source, Rect::new(x, y, canvas.width, canvas.height));
+    }
+
+    // Draws a source rectangle of a canvas scaled into a destination rectangle
+    pub fn draw_canvas_region(&mut self, canvas: &Canvas, source: Rect, destination: Rect) {
+        self.draw_region_of(canvas, source, destination);
+    }
+
+    // Draws a canvas with flipping, rotation and scaling around a pivot at given position
+    pub fn draw_canvas_ex(&mut self, canvas: &Canvas, x: f32, y: f32, params: &DrawParams) {
+        self.draw_transformed_of(canvas, x, y, params);
+    }
+
+    // Draws a frame of a sprite sheet scaled into a destination rectangle
+    pub fn draw_sprite(&mut self, sprite_sheet: &SpriteSheet, index: usize, destination: Rect) {
+        if let Some(source) = sprite_sheet.frame(index) {
+            self.draw_image_region(sprite_sheet.image(), source, destination);
         }
     }
 
-    // Draws a single pixel at specified coordinates
-    pub fn draw_point(&mut self, x: i32, y: i32, color: [u8; 4]) {
-        if x < 0 || y < 0 || x >= self.width as i32 || y >= self.height as i32 {
-            return;
+    // Draws a frame of a sprite sheet with transforms; source in params is ignored
+    pub fn draw_sprite_ex(
+        &mut self,
+        sprite_sheet: &SpriteSheet,
+        index: usize,
+        x: f32,
+        y: f32,
+        params: &DrawParams,
+    ) {
+        if let Some(source) = sprite_sheet.frame(index) {
+            self.draw_image_ex(sprite_sheet.image(), x, y, &params.source(source));
         }
+    }
 
-        let buffer_index = (y as u32 * self.width + x as u32) as usize;
+    // Draws tinted text with a bitmap font at integer scale, breaking lines on '\n'
+    pub fn draw_text(
+        &mut self,
+        font: &BitmapFont,
+        text: &str,
+        x: i32,
+        y: i32,
+        color: [u8; 4],
+        scale: u32,
+    ) {
+        let advance = font.advance(scale) as i32;
+        let line_height = font.line_height(scale) as i32;
+
+        let mut pen_x = x;
+        let mut pen_y = y;
+
+        for character in text.chars() {
+            if character == '\n' {
+                pen_x = x;
+                pen_y += line_height;
+                continue;
+            }
+
+            if !character.is_whitespace() {
+                if let Some(glyph) = font.glyph(character) {
+                    self.draw_glyph(font.image(), glyph, pen_x, pen_y, color, scale);
+                }
+            }
+
+            pen_x += advance;
+        }
+    }
+
+    // Draws anti-aliased text with a TrueType font, top-left at position, breaking lines on '\n'
+    pub fn draw_font_text(
+        &mut self,
+        font: &Font,
+        text: &str,
+        x: f32,
+        y: f32,
+        size: f32,
+        color: [u8; 4],
+    ) {
+        // TrueType text follows camera position and zoom but stays upright
+        let (x, y, size) = match self.view() {
+            Some(view) => {
+                let (x, y) = view.to_screen(x, y);
+                (x, y, size * view.zoom)
+            }
+            None => (x, y, size),
+        };
 
-        self.buffer[buffer_index] = Self::rgba_to_u32(color);
+        let line_height = font.line_height(size);
+
+        for (line_index, line) in text.split('\n').enumerate() {
+            let line_y = y + line_index as f32 * line_height;
+
+            self.draw_font_line(font, line, x, line_y, size, color);
+        }
     }
 
-    // Presents rendered buffer to window
-    pub fn present(&mut self) -> Result<(), RendererError> {
-        let mut surface_buffer = self
-            .surface
-            .buffer_mut()
-            .map_err(|source| RendererError::PresentFailed { source })?;
+    // Draws word-wrapped, aligned text inside bounds; lines past bottom edge are dropped
+    pub fn draw_text_box(
+        &mut self,
+        font: &Font,
+        text: &str,
+        bounds: Rect,
+        size: f32,
+        color: [u8; 4],
+        align: TextAlign,
+    ) {
+        let (bounds, size) = match self.view() {
+            Some(view) => {
+                let (x, y) = view.to_screen(bounds.x as f32, bounds.y as f32);
+                let width = (bounds.width as f32 * view.zoom) as u32;
+                let height = (bounds.height as f32 * view.zoom) as u32;
 
-        surface_buffer.copy_from_slice(&self.buffer);
+                (
+                    Rect::new(x as i32, y as i32, width, height),
+                    size * view.zoom,
+                )
+            }
+            None => (bounds, size),
+        };
 
-        surface_buffer
-            .present()
-            .map_err(|source| RendererError::PresentFailed { source })?;
+        let line_height = font.line_height(size);
+        let mut line_y = bounds.y as f32;
 
-        Ok(())
+        for line in font.wrap(text, size, bounds.width as f32) {
+            if line_y + line_height > bounds.bottom() as f32 {
+                break;
+            }
+
+            let free_width = bounds.width as f32 - font.measure_line(&line, size);
+            let offset_x = match align {
+                TextAlign::Left => 0.0,
+                TextAlign::Center => (free_width / 2.0).floor(),
+                TextAlign::Right => free_width,
+            };
+
+            self.draw_font_line(font, &line, bounds.x as f32 + offset_x, line_y, size, color);
+
+            line_y += line_height;
+        }
+    }
+
+    // Draws a single kerned line of TrueType text with top of line at y
+    fn draw_font_line(
+        &mut self,
+        font: &Font,
+        line: &str,
+        x: f32,
+        y: f32,
+        size: f32,
+        color: [u8; 4],
+    ) {
+        let [r, g, b, a] = color;
+        let baseline = (y + font.ascent(size)).round() as i32;
+
+        let mut pen_x = x;
+        let mut previous = None;
+
+        for character in line.chars() {
+            if let Some(previous) = previous {
+                pen_x += font.kerning(previous, character, size);
+            }
+
+            let glyph = font.glyph(character, size);
+            let metrics = &glyph.metrics;
+            let glyph_x = pen_x.round() as i32 + metrics.xmin;
+            let glyph_y = baseline - metrics.height as i32 - metrics.ymin;
+
+            for row in 0..metrics.height {
+                for column in 0..metrics.width {
+                    let coverage = glyph.coverage[row * metrics.width + column];
+                    if coverage == 0 {
+                        continue;
+                    }
+
+                    let alpha = (a as u32 * coverage as u32 / 255) as u8;
+
+                    self.plot(
+                        glyph_x + column as i32,
+                        glyph_y + row as i32,
+                        [r, g, b, alpha],
+                    );
+                }
+            }
+
+            pen_x += metrics.advance_width;
+            previous = Some(character);
+        }
+    }
+
+    // Draws a glyph using its alpha as coverage for given color
+    fn draw_glyph(
+        &mut self,
+        image: &ImageData,
+        glyph: Rect,
+        x: i32,
+        y: i32,
+        color: [u8; 4],
+        scale: u32,
+    ) {
+        let [r, g, b, a] = color;
+
+        for glyph_y in 0..glyph.height as i32 {
+            for glyph_x in 0..glyph.width as i32 {
+                let Some([_, _, _, coverage]) = image.pixel(glyph.x + glyph_x, glyph.y + glyph_y)
+                else {
+                    continue;
+                };
+
+                if coverage == 0 {
+                    continue;
+                }
+
+                let alpha = (a as u32 * coverage as u32 / 255) as u8;
+
+                self.draw_rectangle(
+                    x + glyph_x * scale as i32,
+                    y + glyph_y * scale as i32,
+                    scale,
+                    scale,
+                    [r, g, b, alpha],
+                );
+            }
+        }
+    }
+
+    // Draws a filled rectangle
+    pub fn draw_rectangle(&mut self, x: i32, y: i32, width: u32, height: u32, color: [u8; 4]) {
+        if self.view().is_some() {
+            let (left, top) = (x as f32, y as f32);
+            let (right, bottom) = (left + width as f32, top + height as f32);
+
+            self.fill_polygon_points(
+                &[(left, top), (right, top), (right, bottom), (left, bottom)],
+                color,
+            );
+            return;
+        }
+
+        let Some(visible) = Rect::new(x, y, width, height).intersection(&self.clip_bounds()) else {
+            return;
+        };
+
+        self.mark_damaged(visible);
+
+        let blend_mode = self.blend_mode;
+        let columns = visible.x as usize..visible.right() as usize;
+
+        raster::for_each_row(
+            &mut self.buffer,
+            self.width,
+            visible.y..visible.bottom(),
+            visible.width,
+            |_, row| raster::blend_fill(&mut row[columns.clone()], blend_mode, color),
+        );
+    }
+
+    // Draws a line between two points
+    pub fn draw_line(&mut self, x1: i32, y1: i32, x2: i32, y2: i32, color: [u8; 4]) {
+        if let Some(view) = self.view() {
+            // Square caps keep end pixels whole when zoomed in
+            let style = LineStyle::new()
+                .thickness(view.pixel_thickness())
+                .cap(LineCap::Square);
+            let points = [
+                view.center_to_raster(x1 as f32, y1 as f32),
+                view.center_to_raster(x2 as f32, y2 as f32),
+            ];
+
+            self.stroke_screen_path(&points, false, &style, color);
+            return;
+        }
+
+        let delta_x = (x2 - x1).abs();
+        let delta_y = -(y2 - y1).abs();
+        let step_x = if x1 < x2 { 1 } else { -1 };
+        let step_y = if y1 < y2 { 1 } else { -1 };
+        let mut error = delta_x + delta_y;
+
+        let mut current_x = x1;
+        let mut current_y = y1;
+
+        loop {
+            self.plot(current_x, current_y, color);
+
+            if current_x == x2 && current_y == y2 {
+                break;
+            }
+
+            let error_doubled = 2 * error;
+            if error_doubled >= delta_y {
+                if current_x == x2 {
+                    break;
+                }
+
+                error += delta_y;
+                current_x += step_x;
+            }
+
+            if error_doubled <= delta_x {
+                if current_y == y2 {
+                    break;
+                }
+
+                error += delta_x;
+                current_y += step_y;
+            }
+        }
+    }
+
+    // Draws a circle outline using midpoint circle
+    pub fn draw_circle(&mut self, center_x: i32, center_y: i32, radius: u32, color: [u8; 4]) {
+        if let Some(view) = self.view() {
+            let points = ellipse_points(center_x, center_y, radius as f32, radius as f32);
+            let points: Vec<(f32, f32)> = points
+                .iter()
+                .map(|&(x, y)| view.center_to_raster(x, y))
+                .collect();
+            let style = LineStyle::new().thickness(view.pixel_thickness());
+
+            self.stroke_screen_path(&points, true, &style, color);
+            return;
+        }
+
+        if radius == 0 {
+            self.plot(center_x, center_y, color);
+            return;
+        }
+
+        let mut offset_x = 0i32;
+        let mut offset_y = radius as i32;
+        let mut decision_parameter = 1 - radius as i32;
+
+        while offset_x <= offset_y {
+            self.draw_circle_quadrants(center_x, center_y, offset_x, offset_y, color);
+
+            // Octants meet on the diagonal, so swapped points would repeat there
+            if offset_x != offset_y {
+                self.draw_circle_quadrants(center_x, center_y, offset_y, offset_x, color);
+            }
+
+            offset_x += 1;
+
+            if decision_parameter < 0 {
+                decision_parameter += 2 * offset_x + 1;
+            } else {
+                offset_y -= 1;
+                decision_parameter += 2 * (offset_x - offset_y) + 1;
+            }
+        }
+    }
+
+    // Draws four mirrored circle points, skipping duplicates on the axes
+    fn draw_circle_quadrants(
+        &mut self,
+        center_x: i32,
+        center_y: i32,
+        offset_x: i32,
+        offset_y: i32,
+        color: [u8; 4],
+    ) {
+        for mirror_x in [offset_x, -offset_x] {
+            self.plot(center_x + mirror_x, center_y + offset_y, color);
+
+            if offset_y != 0 {
+                self.plot(center_x + mirror_x, center_y - offset_y, color);
+            }
+
+            if offset_x == 0 {
+                break;
+            }
+        }
+    }
+
+    // Draws a single pixel at specified coordinates
+    pub fn draw_point(&mut self, x: i32, y: i32, color: [u8; 4]) {
+        if self.view().is_some() {
+            self.draw_rectangle(x, y, 1, 1, color);
+            return;
+        }
+
+        self.plot(x, y, color);
+    }
+
+    // Presents rendered buffer to window; recordings count it as a 60 Hz frame
+    pub fn present(&mut self) -> Result<(), RendererError> {
+        self.present_timed(DEFAULT_FRAME_TIME)
+    }
+
+    // Presents rendered buffer to window, delta_time seconds after previous frame; recordings
+    // use it for their length and frame delays
+    pub fn present_timed(&mut self, delta_time: f32) -> Result<(), RendererError> {
+        if self.is_drawing_to_canvas() {
+            log_warn!(
+                "Renderer",
+                "Skipped present while a canvas target is pushed"
+            );
+            return Ok(());
+        }
+
+        self.apply_post_effects();
+
+        self.record_frame(delta_time);
+
+        let viewport = self.viewport();
+        let shows_post_output = self.shows_post_output();
+
+        let Some(surface) = self.surface.as_mut() else {
+            self.damage.finish_frame();
+            return Ok(());
+        };
+
+        // A surface that was never given a size has no pixels to present into
+        if self.surface_width == 0 || self.surface_height == 0 {
+            return Ok(());
+        }
+
+        // Nothing was drawn since last present, so window already shows this frame
+        if self
+            .damage
+            .current()
+            .is_some_and(|regions| regions.is_empty())
+        {
+            return Ok(());
+        }
+
+        let mut surface_buffer = surface
+            .buffer_mut()
+            .map_err(|source| RendererError::PresentFailed { source })?;
+
+        let output = if shows_post_output {
+            &self.post_buffer
+        } else {
+            &self.buffer
+        };
+
+        // Back buffers may hold an older frame, so everything changed since then is copied
+        match self.damage.since(surface_buffer.age()) {
+            Some(regions) => {
+                for region in regions {
+                    Self::copy_region(
+                        output,
+                        self.width,
+                        &mut surface_buffer,
+                        (self.surface_width, self.surface_height),
+                        viewport,
+                        region,
+                    );
+                }
+            }
+            None if (self.width, self.height) == (self.surface_width, self.surface_height) => {
+                // Surface expects upper 8 bits to be zero, so alpha is stripped here
+                for (target, pixel) in surface_buffer.iter_mut().zip(output) {
+                    *target = pixel & 0x00FF_FFFF;
+                }
+            }
+            None => {
+                let [r, g, b, _a] = self.border_color;
+
+                Self::upscale_into(
+                    output,
+                    self.width,
+                    &mut surface_buffer,
+                    self.surface_width,
+                    viewport,
+                    blend::pack(r, g, b, 0),
+                );
+            }
+        }
+
+        let surface_bounds = Rect::new(0, 0, self.surface_width, self.surface_height);
+        let damaged = match self.damage.current() {
+            Some(regions) => regions
+                .iter()
+                .map(|region| Self::to_surface_rect(*region, viewport))
+                .collect(),
+            None => vec![surface_bounds],
+        };
+
+        let damage_rects: Vec<SurfaceRect> = damaged
+            .iter()
+            .filter_map(|rect| rect.intersection(&surface_bounds))
+            .filter_map(|rect| {
+                Some(SurfaceRect {
+                    x: rect.x as u32,
+                    y: rect.y as u32,
+                    width: NonZeroU32::new(rect.width)?,
+                    height: NonZeroU32::new(rect.height)?,
+                })
+            })
+            .collect();
+
+        surface_buffer
+            .present_with_damage(&damage_rects)
+            .map_err(|source| RendererError::PresentFailed { source })?;
+
+        self.damage.finish_frame();
+
+        Ok(())
+    }
+
+    // Hands presented frame to running recording, and reports recordings that finished writing
+    fn record_frame(&mut self, delta_time: f32) {
+        if self
+            .recorder
+            .as_mut()
+            .is_some_and(|recorder| recorder.wants_frame(delta_time))
+        {
+            let (_, width, height) = self.screen_output();
+            let rgba_bytes = self.to_rgba_bytes();
+
+            if let Some(recorder) = self.recorder.as_mut() {
+                recorder.capture(rgba_bytes, width, height);
+            }
+        }
+
+        if self.recorder.as_ref().is_some_and(Recorder::is_done) {
+            self.stop_recording();
+        }
+
+        // Written recordings are collected without blocking; unfinished ones stay queued
+        let (finished, writing) = mem::take(&mut self.finishing_recordings)
+            .into_iter()
+            .partition(FinishingRecording::is_finished);
+        self.finishing_recordings = writing;
+
+        for finishing in finished {
+            match finishing.wait() {
+                Ok(path) => log_info!("Renderer", "Saved recording to {}", path.display()),
+                Err(error) => log_error!("Renderer", "{}", error),
+            }
+        }
+    }
+
+    // Returns buffer and size of screen, which sits at bottom of target stack while canvases are drawn
+    fn screen_buffer(&self) -> (&[u32], u32, u32) {
+        match self.target_stack.first() {
+            Some((screen, _)) => (&screen.buffer, screen.width, screen.height),
+            None => (&self.buffer, self.width, self.height),
+        }
+    }
+
+    // Returns what screen shows: post-processed output while effects run, otherwise screen buffer
+    fn screen_output(&self) -> (&[u32], u32, u32) {
+        let (buffer, width, height) = self.screen_buffer();
+
+        if self.shows_post_output() {
+            (&self.post_buffer, width, height)
+        } else {
+            (buffer, width, height)
+        }
+    }
+
+    // Returns blend mode, clips and cameras that draws currently use
+    pub(super) fn draw_state(&self) -> DrawState {
+        DrawState {
+            blend_mode: self.blend_mode,
+            clip_stack: self.clip_stack.clone(),
+            camera_stack: self.camera_stack.clone(),
+        }
+    }
+
+    // Swaps blend mode, clips and cameras for given ones, returning those replaced
+    pub(super) fn replace_draw_state(&mut self, state: DrawState) -> DrawState {
+        DrawState {
+            blend_mode: mem::replace(&mut self.blend_mode, state.blend_mode),
+            clip_stack: mem::replace(&mut self.clip_stack, state.clip_stack),
+            camera_stack: mem::replace(&mut self.camera_stack, state.camera_stack),
+        }
+    }
+
+    // Returns world to screen transform of active camera for current draw target
+    pub(super) fn view(&self) -> Option<View> {
+        self.camera_stack
+            .last()
+            .map(|camera| camera.view((self.width, self.height)))
+    }
+
+    // Draws a region of any pixel source, following active camera
+    pub(super) fn draw_region_of(
+        &mut self,
+        image: &impl PixelSource,
+        source: Rect,
+        destination: Rect,
+    ) {
+        if self.view().is_none() {
+            self.blit_region(image, source, destination);
+            return;
+        }
+
+        if source.is_empty() {
+            return;
+        }
+
+        let params = DrawParams::new().source(source).scale(
+            destination.width as f32 / source.width as f32,
+            destination.height as f32 / source.height as f32,
+        );
+
+        self.draw_transformed_of(image, destination.x as f32, destination.y as f32, &params);
+    }
+
+    // Draws any pixel source with transforms, following active camera
+    pub(super) fn draw_transformed_of(
+        &mut self,
+        image: &impl PixelSource,
+        x: f32,
+        y: f32,
+        params: &DrawParams,
+    ) {
+        let Some(view) = self.view() else {
+            self.blit_transformed(image, x, y, params);
+            return;
+        };
+
+        let (screen_x, screen_y) = view.to_screen(x, y);
+        let mut params = *params;
+        params.scale = (params.scale.0 * view.zoom, params.scale.1 * view.zoom);
+        params.rotation += view.rotation;
+
+        self.blit_transformed(image, screen_x, screen_y, &params);
+    }
+
+    // Copies a source rectangle of any pixel source scaled into a destination rectangle
+    fn blit_region(&mut self, image: &impl PixelSource, source: Rect, destination: Rect) {
+        if source.is_empty() {
+            return;
+        }
+
+        let Some(visible) = destination.intersection(&self.clip_bounds()) else {
+            return;
+        };
+
+        self.mark_damaged(visible);
+
+        let blend_mode = self.blend_mode;
+
+        // Source column of every visible destination column, shared by all rows
+        let columns: Vec<i32> = (visible.x..visible.right())
+            .map(|screen_x| {
+                let destination_x = (screen_x - destination.x) as u32;
+                source.x + ((destination_x * source.width) / destination.width) as i32
+            })
+            .collect();
+
+        raster::for_each_row(
+            &mut self.buffer,
+            self.width,
+            visible.y..visible.bottom(),
+            visible.width,
+            |screen_y, row| {
+                let destination_y = (screen_y - destination.y) as u32;
+                let source_y =
+                    source.y + ((destination_y * source.height) / destination.height) as i32;
+                let span = &mut row[visible.x as usize..visible.right() as usize];
+
+                // Parts of source rectangle outside image are treated as transparent
+                match image.row(source_y) {
+                    Some(source_row) => {
+                        for (pixel, &source_x) in span.iter_mut().zip(&columns) {
+                            if let Some(rgba) = source_row.get(source_x) {
+                                *pixel = blend_mode.blend(*pixel, rgba);
+                            }
+                        }
+                    }
+                    None => {
+                        for (pixel, &source_x) in span.iter_mut().zip(&columns) {
+                            if let Some(rgba) = image.sample(source_x, source_y) {
+                                *pixel = blend_mode.blend(*pixel, rgba);
+                            }
+                        }
+                    }
+                }
+            },
+        );
+    }
+
+    // Draws any pixel source with flipping, rotation and scaling around a pivot
+    fn blit_transformed(&mut self, image: &impl PixelSource, x: f32, y: f32, params: &DrawParams) {
+        let (image_width, image_height) = image.size();
+        let source = params
+            .source
+            .unwrap_or(Rect::new(0, 0, image_width, image_height));
+        let (scale_x, scale_y) = params.scale;
+
+        if source.is_empty() || scale_x == 0.0 || scale_y == 0.0 {
+            return;
+        }
+
+        let (sin, cos) = params.rotation_sin_cos();
+        let (pivot_x, pivot_y) = params.pivot;
+        let (source_width, source_height) = (source.width as f32, source.height as f32);
+
+        // Bounding box of transformed source corners limits the pixels to visit
+        let mut minimum = (f32::MAX, f32::MAX);
+        let mut maximum = (f32::MIN, f32::MIN);
+
+        for (corner_x, corner_y) in [
+            (0.0, 0.0),
+            (source_width, 0.0),
+            (0.0, source_height),
+            (source_width, source_height),
+        ] {
+            let local_x = (corner_x - pivot_x) * scale_x;
+            let local_y = (corner_y - pivot_y) * scale_y;
+            let screen_x = x + local_x * cos - local_y * sin;
+            let screen_y = y + local_x * sin + local_y * cos;
+
+            minimum = (minimum.0.min(screen_x), minimum.1.min(screen_y));
+            maximum = (maximum.0.max(screen_x), maximum.1.max(screen_y));
+        }
+
+        let clip = self.clip_bounds();
+        let start_x = (minimum.0.floor() as i32).max(clip.x);
+        let start_y = (minimum.1.floor() as i32).max(clip.y);
+        let end_x = (maximum.0.ceil() as i32).min(clip.right());
+        let end_y = (maximum.1.ceil() as i32).min(clip.bottom());
+
+        if start_x >= end_x || start_y >= end_y {
+            return;
+        }
+
+        self.mark_damaged(Rect::new(
+            start_x,
+            start_y,
+            (end_x - start_x) as u32,
+            (end_y - start_y) as u32,
+        ));
+
+        let blend_mode = self.blend_mode;
+
+        // Source coordinates change linearly along a row by these steps per screen pixel
+        let (step_x, step_y) = (cos / scale_x, -sin / scale_y);
+
+        raster::for_each_row(
+            &mut self.buffer,
+            self.width,
+            start_y..end_y,
+            (end_x - start_x) as u32,
+            |screen_y, row| {
+                // Inverse transform of pixel center at screen column zero back into source space
+                let offset_y = screen_y as f32 + 0.5 - y;
+                let origin_x = ((0.5 - x) * cos + offset_y * sin) / scale_x + pivot_x;
+                let origin_y = (offset_y * cos - (0.5 - x) * sin) / scale_y + pivot_y;
+
+                let (inside_x, inside_y) = (
+                    span_within(origin_x, step_x, source_width),
+                    span_within(origin_y, step_y, source_height),
+                );
+                let span_start = (inside_x.0.max(inside_y.0).floor() as i32).max(start_x);
+                let span_end = (inside_x.1.min(inside_y.1).ceil() as i32).min(end_x);
+
+                for screen_x in span_start..span_end {
+                    // Transformed per pixel rather than stepped so texel edges land exactly
+                    let offset_x = screen_x as f32 + 0.5 - x;
+                    let local_x = (offset_x * cos + offset_y * sin) / scale_x + pivot_x;
+                    let local_y = (offset_y * cos - offset_x * sin) / scale_y + pivot_y;
+
+                    if local_x < 0.0
+                        || local_y < 0.0
+                        || local_x >= source_width
+                        || local_y >= source_height
+                    {
+                        continue;
+                    }
+
+                    let mut sample_x = local_x as i32;
+                    let mut sample_y = local_y as i32;
+
+                    if params.flip_x {
+                        sample_x = source.width as i32 - 1 - sample_x;
+                    }
+
+                    if params.flip_y {
+                        sample_y = source.height as i32 - 1 - sample_y;
+                    }
+
+                    let Some(rgba) = image.sample(source.x + sample_x, source.y + sample_y) else {
+                        continue;
+                    };
+
+                    let pixel = &mut row[screen_x as usize];
+                    *pixel = blend_mode.blend(*pixel, rgba);
+                }
+            },
+        );
+    }
+
+    // Writes source pixels into a larger target with integer scaling and letterboxing
+    fn upscale_into(
+        source: &[u32],
+        source_width: u32,
+        target: &mut [u32],
+        target_width: u32,
+        viewport: (i32, i32, u32),
+        border: u32,
+    ) {
+        let (offset_x, offset_y, scale) = viewport;
+        let source_height = source.len() as u32 / source_width.max(1);
+
+        let scaled_width = (source_width * scale) as i32;
+        let scaled_height = (source_height * scale) as i32;
+
+        for (target_y, target_row) in target.chunks_exact_mut(target_width as usize).enumerate() {
+            let local_y = target_y as i32 - offset_y;
+            if local_y < 0 || local_y >= scaled_height {
+                target_row.fill(border);
+                continue;
+            }
+
+            let row_start = (local_y as u32 / scale * source_width) as usize;
+            let source_row = &source[row_start..row_start + source_width as usize];
+
+            for (target_x, target) in target_row.iter_mut().enumerate() {
+                let local_x = target_x as i32 - offset_x;
+
+                *target = if local_x < 0 || local_x >= scaled_width {
+                    border
+                } else {
+                    source_row[(local_x as u32 / scale) as usize] & 0x00FF_FFFF
+                };
+            }
+        }
+    }
+
+    // Copies a render buffer region into its scaled place on surface, stripping alpha
+    fn copy_region(
+        source: &[u32],
+        source_width: u32,
+        target: &mut [u32],
+        target_size: (u32, u32),
+        viewport: (i32, i32, u32),
+        region: Rect,
+    ) {
+        let (offset_x, offset_y, scale) = viewport;
+        let source_height = source.len() as u32 / source_width.max(1);
+
+        let Some(region) = region.intersection(&Rect::new(0, 0, source_width, source_height))
+        else {
+            return;
+        };
+
+        let Some(area) = Self::to_surface_rect(region, viewport).intersection(&Rect::new(
+            0,
+            0,
+            target_size.0,
+            target_size.1,
+        )) else {
+            return;
+        };
+
+        for target_y in area.y..area.bottom() {
+            let source_y = (target_y - offset_y) as u32 / scale;
+            let source_row = &source[(source_y * source_width) as usize..];
+            let row_start = (target_y as u32 * target_size.0) as usize;
+            let target_row =
+                &mut target[row_start + area.x as usize..row_start + area.right() as usize];
+
+            for (target_x, target) in (area.x..area.right()).zip(target_row) {
+                let source_x = (target_x - offset_x) as u32 / scale;
+                *target = source_row[source_x as usize] & 0x00FF_FFFF;
+            }
+        }
+    }
+
+    // Maps a render buffer rectangle to surface coordinates of given viewport
+    fn to_surface_rect(rect: Rect, viewport: (i32, i32, u32)) -> Rect {
+        let (offset_x, offset_y, scale) = viewport;
+
+        Rect::new(
+            offset_x + rect.x * scale as i32,
+            offset_y + rect.y * scale as i32,
+            rect.width * scale,
+            rect.height * scale,
+        )
+    }
+
+    // Resizes render buffer, keeping existing pixel data where it fits
+    fn resize_buffer(&mut self, width: u32, height: u32) {
+        self.width = width;
+        self.height = height;
+        self.buffer.resize((width * height) as usize, 0);
+        self.post_output_stale = true;
+        self.damage.add_all();
+    }
+
+    // Resizes window surface to given physical size
+    fn resize_surface(&mut self, width: u32, height: u32) -> Result<(), RendererError> {
+        if let Some(surface) = self.surface.as_mut() {
+            // Surfaces cannot be zero-sized, so size is only stored once surface really resized
+            let (Some(width), Some(height)) = (NonZeroU32::new(width), NonZeroU32::new(height))
+            else {
+                return Ok(());
+            };
+
+            surface
+                .resize(width, height)
+                .map_err(|source| RendererError::ResizeFailed {
+                    width: width.get(),
+                    height: height.get(),
+                    source,
+                })?;
+        }
+
+        self.surface_width = width;
+        self.surface_height = height;
+        self.damage.add_all();
+
+        Ok(())
+    }
+
+    // Blends a color into a single pixel, ignoring coordinates outside clip
+    pub(super) fn plot(&mut self, x: i32, y: i32, color: [u8; 4]) {
+        if !self.clip_bounds().contains(x, y) {
+            return;
+        }
+
+        self.mark_damaged(Rect::new(x, y, 1, 1));
+
+        let buffer_index = (y as u32 * self.width + x as u32) as usize;
+
+        self.blend_pixel(buffer_index, color);
+    }
+
+    // Blends a color into a horizontal run of pixels from start up to (not including) end
+    pub(super) fn fill_span(&mut self, y: i32, start_x: i32, end_x: i32, color: [u8; 4]) {
+        let clip = self.clip_bounds();
+
+        if y < clip.y || y >= clip.bottom() {
+            return;
+        }
+
+        let start_x = start_x.max(clip.x);
+        let end_x = end_x.min(clip.right());
+
+        if start_x >= end_x {
+            return;
+        }
+
+        self.mark_damaged(Rect::new(start_x, y, (end_x - start_x) as u32, 1));
+
+        let row_start = (y as u32 * self.width) as usize;
+        let span = &mut self.buffer[row_start + start_x as usize..row_start + end_x as usize];
+
+        raster::blend_fill(span, self.blend_mode, color);
+    }
+
+    // Records a changed screen area; canvas targets are not presented, so their changes are not tracked
+    fn mark_damaged(&mut self, rect: Rect) {
+        if !self.is_drawing_to_canvas() {
+            self.damage.add(rect);
+        }
+    }
+
+    // Blends a color into buffer at given index using current blend mode
+    fn blend_pixel(&mut self, buffer_index: usize, color: [u8; 4]) {
+        let destination = self.buffer[buffer_index];
+
+        self.buffer[buffer_index] = self.blend_mode.blend(destination, color);
+    }
+}
+
+impl Drop for Renderer {
+    // Waits for recordings still being written so files are complete when program exits
+    fn drop(&mut self) {
+        if let Err(error) = self.finish_recordings() {
+            log_error!("Renderer", "{}", error);
+        }
+    }
+}
+
+// Returns range of steps along a row where origin plus step times slope lies within 0 to length
+fn span_within(origin: f32, slope: f32, length: f32) -> (f32, f32) {
+    if slope == 0.0 {
+        return match (0.0..length).contains(&origin) {
+            true => (f32::MIN, f32::MAX),
+            false => (0.0, 0.0),
+        };
+    }
+
+    let (first, second) = (-origin / slope, (length - origin) / slope);
+
+    // Widened by a step on both sides; exact bounds are still checked per pixel
+    (first.min(second) - 1.0, first.max(second) + 1.0)
+}
+
+// Returns outline points of an ellipse around a pixel center, one every couple of pixels
+pub(super) fn ellipse_points(
+    center_x: i32,
+    center_y: i32,
+    radius_x: f32,
+    radius_y: f32,
+) -> Vec<(f32, f32)> {
+    let segments = ((radius_x.max(radius_y) * 3.0).ceil() as usize).clamp(8, 1024);
+
+    (0..segments)
+        .map(|step| {
+            let angle = step as f32 / segments as f32 * std::f32::consts::TAU;
+            let (sin, cos) = angle.sin_cos();
+
+            (
+                center_x as f32 + radius_x * cos,
+                center_y as f32 + radius_y * sin,
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::renderer::{Bloom, Crt};
+
+    const WHITE: [u8; 4] = [255, 255, 255, 255];
+    const CLEAR: [u8; 4] = [0, 0, 0, 0];
+
+    // Lists positions of pixels that were drawn to
+    fn drawn_pixels(renderer: &Renderer) -> Vec<(i32, i32)> {
+        let (width, height) = (renderer.width() as i32, renderer.height() as i32);
+
+        (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .filter(|&(x, y)| renderer.pixel(x, y) != Some(CLEAR))
+            .collect()
+    }
+
+    // Corner colors of a 2x2 test image, read row by row as A B / C D
+    const QUADRANTS: [(char, [u8; 4]); 4] = [
+        ('A', [255, 0, 0, 255]),
+        ('B', [0, 255, 0, 255]),
+        ('C', [0, 0, 255, 255]),
+        ('D', [255, 255, 255, 255]),
+    ];
+
+    // Creates a 2x2 image with a distinct opaque color in each pixel
+    fn quadrant_image() -> ImageData {
+        ImageData::from_bytes(
+            QUADRANTS.iter().flat_map(|(_, color)| *color).collect(),
+            2,
+            2,
+        )
+    }
+
+    // Spells out which quadrant each pixel shows, row by row, with '.' for untouched pixels
+    fn quadrant_rows(renderer: &Renderer) -> Vec<String> {
+        (0..renderer.height() as i32)
+            .map(|y| {
+                (0..renderer.width() as i32)
+                    .map(|x| {
+                        let pixel = renderer.pixel(x, y);
+
+                        QUADRANTS
+                            .iter()
+                            .find(|(_, color)| Some(*color) == pixel)
+                            .map_or('.', |(name, _)| *name)
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn nested_clips_intersect_and_restore() {
+        let mut renderer = Renderer::offscreen(8, 8);
+
+        renderer.push_clip(Rect::new(0, 0, 6, 6));
+        renderer.push_clip(Rect::new(4, 4, 6, 6));
+        assert_eq!(renderer.clip_bounds(), Rect::new(4, 4, 2, 2));
+
+        renderer.draw_rectangle(0, 0, 8, 8, WHITE);
+        assert_eq!(
+            drawn_pixels(&renderer),
+            vec![(4, 4), (5, 4), (4, 5), (5, 5)]
+        );
+
+        assert_eq!(renderer.pop_clip(), Some(Rect::new(4, 4, 2, 2)));
+        assert_eq!(renderer.clip_bounds(), Rect::new(0, 0, 6, 6));
+        renderer.pop_clip();
+        assert_eq!(renderer.clip_bounds(), Rect::new(0, 0, 8, 8));
+    }
+
+    #[test]
+    fn disjoint_clips_leave_nothing_to_draw() {
+        let mut renderer = Renderer::offscreen(8, 8);
+
+        renderer.push_clip(Rect::new(0, 0, 2, 2));
+        renderer.push_clip(Rect::new(4, 4, 2, 2));
+        assert_eq!(renderer.clip_bounds().width, 0);
+
+        renderer.draw_rectangle(0, 0, 8, 8, WHITE);
+        renderer.draw_line(0, 0, 7, 7, WHITE);
+        renderer.fill_circle(4, 4, 4, WHITE);
+        renderer.draw_text(BitmapFont::embedded(), "#", 0, 0, WHITE, 2);
+
+        assert!(drawn_pixels(&renderer).is_empty());
+    }
+
+    type Draw = fn(&mut Renderer, &ImageData);
+
+    #[test]
+    fn every_primitive_stays_inside_clip() {
+        let clip = Rect::new(4, 4, 8, 8);
+        let image = ImageData::from_bytes(WHITE.repeat(24 * 24), 24, 24);
+        let draws: [(&str, Draw); 5] = [
+            ("rectangle", |renderer, _| {
+                renderer.draw_rectangle(0, 0, 24, 24, WHITE)
+            }),
+            ("line", |renderer, _| {
+                renderer.draw_line_styled(
+                    0.0,
+                    0.0,
+                    23.0,
+                    23.0,
+                    &LineStyle::new().thickness(5.0),
+                    WHITE,
+                )
+            }),
+            ("circle", |renderer, _| {
+                renderer.fill_circle(8, 8, 12, WHITE)
+            }),
+            ("image", |renderer, image| {
+                renderer.draw_image(image, 0, 0, 24)
+            }),
+            ("text", |renderer, _| {
+                renderer.draw_text(BitmapFont::embedded(), "#", 0, 0, WHITE, 4)
+            }),
+        ];
+
+        for (name, draw) in draws {
+            let mut renderer = Renderer::offscreen(24, 24);
+            renderer.push_clip(clip);
+            draw(&mut renderer, &image);
+
+            let drawn = drawn_pixels(&renderer);
+            let outside = drawn.iter().filter(|&&(x, y)| !clip.contains(x, y)).count();
+
+            assert!(!drawn.is_empty(), "{} drew nothing", name);
+            assert_eq!(outside, 0, "{} drew outside clip", name);
+        }
+    }
+
+    #[test]
+    fn draws_are_tracked_as_damage_until_present() {
+        let mut renderer = Renderer::offscreen(16, 16);
+        assert_eq!(renderer.damaged_regions(), vec![Rect::new(0, 0, 16, 16)]);
+
+        renderer.present().unwrap();
+        assert!(renderer.damaged_regions().is_empty());
+
+        renderer.draw_rectangle(2, 3, 4, 5, WHITE);
+        renderer.draw_rectangle(15, 15, 1, 1, WHITE);
+        assert_eq!(
+            renderer.damaged_regions(),
+            vec![Rect::new(2, 3, 4, 5), Rect::new(15, 15, 1, 1)]
+        );
+
+        renderer.present().unwrap();
+        assert!(renderer.damaged_regions().is_empty());
+    }
+
+    #[test]
+    fn image_region_scales_source_rect_into_destination() {
+        let mut renderer = Renderer::offscreen(4, 4);
+
+        renderer.draw_image_region(
+            &quadrant_image(),
+            Rect::new(1, 0, 1, 2),
+            Rect::new(1, 0, 2, 4),
+        );
+
+        assert_eq!(quadrant_rows(&renderer), [".BB.", ".BB.", ".DD.", ".DD."]);
+    }
+
+    #[test]
+    fn image_region_skips_source_outside_image_and_destination_off_screen() {
+        let image = quadrant_image();
+
+        // Source hanging past image edge leaves its outside part transparent
+        let mut renderer = Renderer::offscreen(3, 3);
+        renderer.draw_image_region(&image, Rect::new(1, 1, 2, 2), Rect::new(0, 0, 2, 2));
+        assert_eq!(quadrant_rows(&renderer), ["D..", "...", "..."]);
+
+        // Destination hanging off screen keeps source aligned to its unclipped position
+        let mut renderer = Renderer::offscreen(3, 3);
+        renderer.draw_image_region(&image, Rect::new(0, 0, 2, 2), Rect::new(-1, -1, 4, 4));
+        assert_eq!(quadrant_rows(&renderer), ["ABB", "CDD", "CDD"]);
+
+        let mut renderer = Renderer::offscreen(3, 3);
+        renderer.draw_image_region(&image, Rect::new(0, 0, 2, 2), Rect::new(2, 2, 2, 2));
+        assert_eq!(quadrant_rows(&renderer), ["...", "...", "..A"]);
+    }
+
+    // Draws quadrant image with given parameters onto a fresh 4x4 renderer and spells it out
+    fn transformed_rows(x: f32, y: f32, params: DrawParams) -> Vec<String> {
+        let mut renderer = Renderer::offscreen(4, 4);
+        renderer.draw_image_ex(&quadrant_image(), x, y, &params);
+
+        quadrant_rows(&renderer)
+    }
+
+    #[test]
+    fn transformed_image_flips_within_its_bounds() {
+        let rows = |params| transformed_rows(1.0, 1.0, params);
+
+        assert_eq!(rows(DrawParams::new()), ["....", ".AB.", ".CD.", "...."]);
+        assert_eq!(
+            rows(DrawParams::new().flip_x(true)),
+            ["....", ".BA.", ".DC.", "...."]
+        );
+        assert_eq!(
+            rows(DrawParams::new().flip_y(true)),
+            ["....", ".CD.", ".AB.", "...."]
+        );
+        assert_eq!(
+            rows(DrawParams::new().flip_x(true).flip_y(true)),
+            ["....", ".DC.", ".BA.", "...."]
+        );
+    }
+
+    #[test]
+    fn quarter_turns_rotate_clockwise_around_pivot() {
+        let rows = |turns| {
+            transformed_rows(
+                2.0,
+                2.0,
+                DrawParams::new().pivot(1.0, 1.0).quarter_turns(turns),
+            )
+        };
+
+        assert_eq!(rows(0), ["....", ".AB.", ".CD.", "...."]);
+        assert_eq!(rows(1), ["....", ".CA.", ".DB.", "...."]);
+        assert_eq!(rows(2), ["....", ".DC.", ".BA.", "...."]);
+        assert_eq!(rows(3), ["....", ".BD.", ".AC.", "...."]);
+        assert_eq!(rows(4), rows(0));
+    }
+
+    #[test]
+    fn quarter_turn_without_centered_pivot_swings_around_corner() {
+        // Pivot defaults to top-left corner, so turning swings image around draw position
+        assert_eq!(
+            transformed_rows(2.0, 1.0, DrawParams::new().quarter_turns(1)),
+            ["....", "CA..", "DB..", "...."]
+        );
+        assert_eq!(
+            transformed_rows(2.0, 2.0, DrawParams::new().quarter_turns(2)),
+            ["DC..", "BA..", "....", "...."]
+        );
+    }
+
+    #[test]
+    fn arbitrary_rotation_matches_quarter_turn_at_right_angles() {
+        let quarter =
+            transformed_rows(2.0, 2.0, DrawParams::new().pivot(1.0, 1.0).quarter_turns(1));
+        let rotated = transformed_rows(
+            2.0,
+            2.0,
+            DrawParams::new()
+                .pivot(1.0, 1.0)
+                .rotation(std::f32::consts::FRAC_PI_2),
+        );
+
+        assert_eq!(rotated, quarter);
+    }
+
+    #[test]
+    fn pivot_and_scale_place_image_relative_to_draw_position() {
+        assert_eq!(
+            transformed_rows(3.0, 3.0, DrawParams::new().pivot(1.0, 1.0)),
+            ["....", "....", "..AB", "..CD"]
+        );
+        assert_eq!(
+            transformed_rows(0.0, 1.0, DrawParams::new().scale(2.0, 1.0)),
+            ["....", "AABB", "CCDD", "...."]
+        );
+        assert_eq!(
+            transformed_rows(2.0, 2.0, DrawParams::new().pivot(1.0, 1.0).scale(1.0, 2.0)),
+            [".AB.", ".AB.", ".CD.", ".CD."]
+        );
+    }
+
+    #[test]
+    fn transformed_source_rect_picks_sub_image_and_flips_inside_it() {
+        let params = DrawParams::new().source(Rect::new(0, 0, 2, 1));
+
+        assert_eq!(
+            transformed_rows(0.0, 0.0, params),
+            ["AB..", "....", "....", "...."]
+        );
+        assert_eq!(
+            transformed_rows(0.0, 0.0, params.flip_x(true)),
+            ["BA..", "....", "....", "...."]
+        );
+    }
+
+    // Draws a scene mixing every row-split primitive over areas big enough to run in parallel
+    fn render_scene() -> Renderer {
+        let mut renderer = Renderer::offscreen(320, 240);
+        let image = ImageData::from_bytes(
+            (0..64 * 64)
+                .flat_map(|index: u32| [index as u8, (index / 64) as u8 * 4, 96, 200])
+                .collect(),
+            64,
+            64,
+        );
+
+        renderer.add_post_effect(Crt::default());
+        renderer.add_post_effect(Bloom::default());
+        renderer.clear([12, 24, 48, 255]);
+        renderer.draw_rectangle(-20, 10, 360, 200, [200, 120, 40, 128]);
+        renderer.set_blend_mode(BlendMode::Additive);
+        renderer.draw_image_region(&image, Rect::new(8, 8, 48, 48), Rect::new(0, 0, 320, 240));
+        renderer.set_blend_mode(BlendMode::Alpha);
+        renderer.draw_image_ex(
+            &image,
+            160.0,
+            120.0,
+            &DrawParams::new()
+                .pivot(32.0, 32.0)
+                .scale(3.5, 2.5)
+                .rotation(0.6)
+                .flip_x(true),
+        );
+        renderer.fill_circle(100, 100, 60, [255, 255, 255, 90]);
+        renderer.draw_line_styled(
+            0.0,
+            239.0,
+            319.0,
+            0.0,
+            &LineStyle::new().thickness(7.0),
+            [250, 250, 250, 255],
+        );
+        renderer.present().unwrap();
+
+        renderer
     }
 
-    // Converts RGBA color to u32 format for buffer
-    fn rgba_to_u32(rgba: [u8; 4]) -> u32 {
-        let [r, g, b, _a] = rgba;
+    #[test]
+    fn parallel_rendering_matches_serial_rendering() {
+        let serial = raster::serially(render_scene);
+        let parallel = render_scene();
 
-        ((r as u32) << 16) | ((g as u32) << 8) | (b as u32)
+        assert!(serial.pixels() == parallel.pixels());
+        assert!(serial.screenshot().rgba_bytes == parallel.screenshot().rgba_bytes);
     }
 }
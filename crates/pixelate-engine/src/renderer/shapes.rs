@@ -0,0 +1,492 @@
+use super::{renderer::ellipse_points, LineStyle, Renderer};
+
+impl Renderer {
+    // Draws a one pixel wide rectangle outline
+    pub fn draw_rectangle_outline(
+        &mut self,
+        x: i32,
+        y: i32,
+        width: u32,
+        height: u32,
+        color: [u8; 4],
+    ) {
+        if width == 0 || height == 0 {
+            return;
+        }
+
+        // Strips are drawn as separate rectangles so they tile without overlap when rotated
+        if self.view().is_some() {
+            let inner_height = height.saturating_sub(2);
+
+            self.draw_rectangle(x, y, width, 1, color);
+
+            if height > 1 {
+                self.draw_rectangle(x, y + height as i32 - 1, width, 1, color);
+            }
+
+            self.draw_rectangle(x, y + 1, 1, inner_height, color);
+
+            if width > 1 {
+                self.draw_rectangle(x + width as i32 - 1, y + 1, 1, inner_height, color);
+            }
+
+            return;
+        }
+
+        let right = x + width as i32 - 1;
+        let bottom = y + height as i32 - 1;
+
+        self.fill_span(y, x, right + 1, color);
+
+        if bottom != y {
+            self.fill_span(bottom, x, right + 1, color);
+        }
+
+        for row in y + 1..bottom {
+            self.plot(x, row, color);
+
+            if right != x {
+                self.plot(right, row, color);
+            }
+        }
+    }
+
+    // Draws a filled circle matching extent of draw_circle
+    pub fn fill_circle(&mut self, center_x: i32, center_y: i32, radius: u32, color: [u8; 4]) {
+        if self.view().is_some() {
+            self.fill_ellipse(center_x, center_y, radius, radius, color);
+            return;
+        }
+
+        let half_widths = circle_half_widths(radius);
+
+        for (offset_y, &half_width) in half_widths.iter().enumerate() {
+            let offset_y = offset_y as i32;
+            let (start_x, end_x) = (center_x - half_width, center_x + half_width + 1);
+
+            self.fill_span(center_y + offset_y, start_x, end_x, color);
+
+            if offset_y != 0 {
+                self.fill_span(center_y - offset_y, start_x, end_x, color);
+            }
+        }
+    }
+
+    // Draws an axis-aligned ellipse outline using midpoint ellipse
+    pub fn draw_ellipse(
+        &mut self,
+        center_x: i32,
+        center_y: i32,
+        radius_x: u32,
+        radius_y: u32,
+        color: [u8; 4],
+    ) {
+        if let Some(view) = self.view() {
+            let points: Vec<(f32, f32)> =
+                ellipse_points(center_x, center_y, radius_x as f32, radius_y as f32)
+                    .iter()
+                    .map(|&(x, y)| view.center_to_raster(x, y))
+                    .collect();
+            let style = LineStyle::new().thickness(view.pixel_thickness());
+
+            self.stroke_screen_path(&points, true, &style, color);
+            return;
+        }
+
+        visit_ellipse_quadrant(radius_x, radius_y, |offset_x, offset_y| {
+            for mirror_x in [offset_x, -offset_x] {
+                self.plot(center_x + mirror_x, center_y + offset_y, color);
+
+                if offset_y != 0 {
+                    self.plot(center_x + mirror_x, center_y - offset_y, color);
+                }
+
+                if offset_x == 0 {
+                    break;
+                }
+            }
+        });
+    }
+
+    // Draws a filled axis-aligned ellipse matching extent of draw_ellipse
+    pub fn fill_ellipse(
+        &mut self,
+        center_x: i32,
+        center_y: i32,
+        radius_x: u32,
+        radius_y: u32,
+        color: [u8; 4],
+    ) {
+        // Outline of pixel centers grows by half a pixel to cover whole edge pixels
+        if self.view().is_some() {
+            let points: Vec<(f32, f32)> = ellipse_points(
+                center_x,
+                center_y,
+                radius_x as f32 + 0.5,
+                radius_y as f32 + 0.5,
+            )
+            .iter()
+            .map(|&(x, y)| (x + 0.5, y + 0.5))
+            .collect();
+
+            self.fill_polygon_points(&points, color);
+            return;
+        }
+
+        let mut half_widths = vec![0; radius_y as usize + 1];
+
+        visit_ellipse_quadrant(radius_x, radius_y, |offset_x, offset_y| {
+            let half_width = &mut half_widths[offset_y as usize];
+            *half_width = (*half_width).max(offset_x);
+        });
+
+        for (offset_y, &half_width) in half_widths.iter().enumerate() {
+            let offset_y = offset_y as i32;
+            let (start_x, end_x) = (center_x - half_width, center_x + half_width + 1);
+
+            self.fill_span(center_y + offset_y, start_x, end_x, color);
+
+            if offset_y != 0 {
+                self.fill_span(center_y - offset_y, start_x, end_x, color);
+            }
+        }
+    }
+
+    // Draws a one pixel wide rectangle outline with rounded corners
+    pub fn draw_rounded_rectangle(
+        &mut self,
+        x: i32,
+        y: i32,
+        width: u32,
+        height: u32,
+        radius: u32,
+        color: [u8; 4],
+    ) {
+        if width == 0 || height == 0 {
+            return;
+        }
+
+        let radius = radius.min(width.min(height) / 2);
+
+        if let Some(view) = self.view() {
+            let points: Vec<(f32, f32)> = rounded_rectangle_points(
+                (x as f32, y as f32),
+                ((width - 1) as f32, (height - 1) as f32),
+                radius.saturating_sub(1) as f32,
+            )
+            .iter()
+            .map(|&(x, y)| view.center_to_raster(x, y))
+            .collect();
+            let style = LineStyle::new().thickness(view.pixel_thickness());
+
+            self.stroke_screen_path(&points, true, &style, color);
+            return;
+        }
+
+        let outer_half_widths = circle_half_widths(radius);
+        let inner_half_widths = circle_half_widths(radius.saturating_sub(1));
+
+        // Outline is outer shape minus the same shape inset by one pixel
+        for row in 0..height {
+            let (outer_start, outer_end) =
+                rounded_row_span(x, width, height, radius, &outer_half_widths, row);
+
+            let inner_span = if row == 0 || row + 1 >= height || width <= 2 {
+                None
+            } else {
+                Some(rounded_row_span(
+                    x + 1,
+                    width - 2,
+                    height - 2,
+                    radius.saturating_sub(1),
+                    &inner_half_widths,
+                    row - 1,
+                ))
+            };
+
+            match inner_span {
+                Some((inner_start, inner_end)) if inner_start < inner_end => {
+                    self.fill_span(y + row as i32, outer_start, inner_start, color);
+                    self.fill_span(y + row as i32, inner_end, outer_end, color);
+                }
+                _ => self.fill_span(y + row as i32, outer_start, outer_end, color),
+            }
+        }
+    }
+
+    // Draws a filled rectangle with rounded corners
+    pub fn fill_rounded_rectangle(
+        &mut self,
+        x: i32,
+        y: i32,
+        width: u32,
+        height: u32,
+        radius: u32,
+        color: [u8; 4],
+    ) {
+        if width == 0 || height == 0 {
+            return;
+        }
+
+        let radius = radius.min(width.min(height) / 2);
+
+        if self.view().is_some() {
+            let points = rounded_rectangle_points(
+                (x as f32, y as f32),
+                (width as f32, height as f32),
+                radius as f32,
+            );
+
+            self.fill_polygon_points(&points, color);
+            return;
+        }
+
+        let half_widths = circle_half_widths(radius);
+
+        for row in 0..height {
+            let (start_x, end_x) = rounded_row_span(x, width, height, radius, &half_widths, row);
+
+            self.fill_span(y + row as i32, start_x, end_x, color);
+        }
+    }
+
+    // Draws a filled triangle
+    #[allow(clippy::too_many_arguments)]
+    pub fn fill_triangle(
+        &mut self,
+        x1: i32,
+        y1: i32,
+        x2: i32,
+        y2: i32,
+        x3: i32,
+        y3: i32,
+        color: [u8; 4],
+    ) {
+        self.fill_polygon(&[(x1, y1), (x2, y2), (x3, y3)], color);
+    }
+
+    // Draws a filled polygon, convex or concave, using even-odd scanline fill
+    pub fn fill_polygon(&mut self, points: &[(i32, i32)], color: [u8; 4]) {
+        let points: Vec<(f32, f32)> = points.iter().map(|&(x, y)| (x as f32, y as f32)).collect();
+
+        self.fill_polygon_points(&points, color);
+    }
+
+    // Fills polygon with continuous vertices, where pixel edges are integers, following active camera
+    pub(super) fn fill_polygon_points(&mut self, points: &[(f32, f32)], color: [u8; 4]) {
+        let view = self.view();
+        let points: Vec<(f32, f32)> = points
+            .iter()
+            .map(|&(x, y)| match view {
+                Some(view) => view.to_raster(x, y),
+                None => (x - 0.5, y - 0.5),
+            })
+            .collect();
+
+        self.fill_screen_polygon(&points, color);
+    }
+
+    // Fills polygon with sub-pixel vertices, sampling pixel centers on each row
+    pub(super) fn fill_screen_polygon(&mut self, points: &[(f32, f32)], color: [u8; 4]) {
+        if points.len() < 3 {
+            return;
+        }
+
+        let min_y = points.iter().map(|&(_, y)| y).fold(f32::MAX, f32::min);
+        let max_y = points.iter().map(|&(_, y)| y).fold(f32::MIN, f32::max);
+
+        let clip = self.clip_bounds();
+        let first_row = (min_y.ceil() as i32).max(clip.y);
+        let last_row = (max_y.ceil() as i32).min(clip.bottom());
+
+        let mut crossings: Vec<f32> = Vec::with_capacity(points.len());
+
+        // Rows sample pixel centers, so bottom and right edges are left open like rectangles
+        for row in first_row..last_row {
+            let sample_y = row as f32;
+            crossings.clear();
+
+            for (index, &(start_x, start_y)) in points.iter().enumerate() {
+                let (end_x, end_y) = points[(index + 1) % points.len()];
+
+                if start_y == end_y
+                    || sample_y < start_y.min(end_y)
+                    || sample_y >= start_y.max(end_y)
+                {
+                    continue;
+                }
+
+                let progress = (sample_y - start_y) / (end_y - start_y);
+                crossings.push(start_x + progress * (end_x - start_x));
+            }
+
+            crossings.sort_by(f32::total_cmp);
+
+            for pair in crossings.chunks_exact(2) {
+                self.fill_span(row, pair[0].ceil() as i32, pair[1].ceil() as i32, color);
+            }
+        }
+    }
+}
+
+// Returns outline points of a rectangle with quarter circle corners
+fn rounded_rectangle_points(origin: (f32, f32), size: (f32, f32), radius: f32) -> Vec<(f32, f32)> {
+    let (left, top) = origin;
+    let (right, bottom) = (left + size.0, top + size.1);
+    let steps = (radius.ceil() as usize).clamp(1, 64);
+
+    let corners = [
+        (right - radius, bottom - radius, 0.0),
+        (left + radius, bottom - radius, 0.25),
+        (left + radius, top + radius, 0.5),
+        (right - radius, top + radius, 0.75),
+    ];
+
+    let mut points = Vec::with_capacity(corners.len() * (steps + 1));
+
+    for (center_x, center_y, start_turn) in corners {
+        for step in 0..=steps {
+            let angle = (start_turn + step as f32 / steps as f32 * 0.25) * std::f32::consts::TAU;
+            let (sin, cos) = angle.sin_cos();
+
+            points.push((center_x + radius * cos, center_y + radius * sin));
+        }
+    }
+
+    points
+}
+
+// Returns half span width for each row offset of a midpoint circle
+fn circle_half_widths(radius: u32) -> Vec<i32> {
+    let mut half_widths = vec![0; radius as usize + 1];
+
+    let mut offset_x = 0i32;
+    let mut offset_y = radius as i32;
+    let mut decision_parameter = 1 - radius as i32;
+
+    while offset_x <= offset_y {
+        let row = &mut half_widths[offset_y as usize];
+        *row = (*row).max(offset_x);
+
+        let row = &mut half_widths[offset_x as usize];
+        *row = (*row).max(offset_y);
+
+        offset_x += 1;
+
+        if decision_parameter < 0 {
+            decision_parameter += 2 * offset_x + 1;
+        } else {
+            offset_y -= 1;
+            decision_parameter += 2 * (offset_x - offset_y) + 1;
+        }
+    }
+
+    half_widths
+}
+
+// Returns horizontal span of a rounded rectangle row as start and exclusive end
+fn rounded_row_span(
+    x: i32,
+    width: u32,
+    height: u32,
+    radius: u32,
+    half_widths: &[i32],
+    row: u32,
+) -> (i32, i32) {
+    let corner_offset = if row < radius {
+        Some(radius - row)
+    } else if row + radius >= height {
+        Some(row + radius + 1 - height)
+    } else {
+        None
+    };
+
+    let inset = corner_offset.map_or(0, |offset| {
+        radius as i32 - half_widths.get(offset as usize).copied().unwrap_or(0)
+    });
+
+    (x + inset, x + width as i32 - inset)
+}
+
+// Visits first quadrant points of a midpoint ellipse, each exactly once
+fn visit_ellipse_quadrant(radius_x: u32, radius_y: u32, mut visit: impl FnMut(i32, i32)) {
+    if radius_y == 0 {
+        (0..=radius_x as i32).for_each(|offset_x| visit(offset_x, 0));
+        return;
+    }
+
+    let (radius_x, radius_y) = (radius_x as i64, radius_y as i64);
+    let (radius_x_squared, radius_y_squared) = (radius_x * radius_x, radius_y * radius_y);
+
+    let mut offset_x = 0i64;
+    let mut offset_y = radius_y;
+    let mut step_x = 0i64;
+    let mut step_y = 2 * radius_x_squared * offset_y;
+
+    // Decision parameters are scaled by four to stay in integers
+    let mut decision_parameter =
+        4 * radius_y_squared - 4 * radius_x_squared * radius_y + radius_x_squared;
+
+    while step_x < step_y {
+        visit(offset_x as i32, offset_y as i32);
+
+        offset_x += 1;
+        step_x += 2 * radius_y_squared;
+
+        if decision_parameter < 0 {
+            decision_parameter += 4 * (radius_y_squared + step_x);
+        } else {
+            offset_y -= 1;
+            step_y -= 2 * radius_x_squared;
+            decision_parameter += 4 * (radius_y_squared + step_x - step_y);
+        }
+    }
+
+    decision_parameter = radius_y_squared * (2 * offset_x + 1) * (2 * offset_x + 1)
+        + 4 * radius_x_squared * (offset_y - 1) * (offset_y - 1)
+        - 4 * radius_x_squared * radius_y_squared;
+
+    while offset_y >= 0 {
+        visit(offset_x as i32, offset_y as i32);
+
+        offset_y -= 1;
+        step_y -= 2 * radius_x_squared;
+
+        if decision_parameter > 0 {
+            decision_parameter += 4 * (radius_x_squared - step_y);
+        } else {
+            offset_x += 1;
+            step_x += 2 * radius_y_squared;
+            decision_parameter += 4 * (radius_x_squared - step_y + step_x);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::renderer::{Camera2D, Renderer};
+
+    const WHITE: [u8; 4] = [255, 255, 255, 255];
+
+    #[test]
+    fn zoomed_out_rounded_outline_stays_solid_and_one_pixel_wide() {
+        let mut renderer = Renderer::offscreen(16, 16);
+        let mut camera = Camera2D::new((8.0, 8.0));
+        camera.zoom = 0.5;
+
+        renderer.push_camera(camera);
+        renderer.draw_rounded_rectangle(0, 0, 16, 16, 4, WHITE);
+
+        // World rectangle shrinks to screen pixels 4 to 11, corners rounded off
+        for edge in 5..11 {
+            assert_eq!(renderer.pixel(edge, 4), Some(WHITE));
+            assert_eq!(renderer.pixel(edge, 11), Some(WHITE));
+            assert_eq!(renderer.pixel(4, edge), Some(WHITE));
+            assert_eq!(renderer.pixel(11, edge), Some(WHITE));
+            assert_eq!(renderer.pixel(edge, 5), Some([0, 0, 0, 0]));
+        }
+
+        assert_eq!(renderer.pixel(4, 4), Some([0, 0, 0, 0]));
+        assert_eq!(renderer.pixel(3, 8), Some([0, 0, 0, 0]));
+    }
+}
@@ -0,0 +1,134 @@
+use super::{canvas::PixelSource, Rect, Renderer};
+use crate::{
+    assets::ImageData,
+    tilemap::{Chunk, Tile, TileMap},
+};
+
+// Single map tile resolved to tileset frame it currently shows
+struct TileSource<'a> {
+    tileset: &'a ImageData,
+    frame: Rect,
+    tile: Tile,
+}
+
+impl PixelSource for TileSource<'_> {
+    fn size(&self) -> (u32, u32) {
+        (self.frame.width, self.frame.height)
+    }
+
+    fn sample(&self, x: i32, y: i32) -> Option<[u8; 4]> {
+        if x < 0 || y < 0 || x >= self.frame.width as i32 || y >= self.frame.height as i32 {
+            return None;
+        }
+
+        let (offset_x, offset_y) =
+            self.tile
+                .source_offset(x as u32, y as u32, self.frame.width, self.frame.height)?;
+
+        self.tileset.pixel(
+            self.frame.x + offset_x as i32,
+            self.frame.y + offset_y as i32,
+        )
+    }
+}
+
+impl Renderer {
+    // Draws visible tiles of every visible layer with map top-left at given position
+    pub fn draw_tile_map(&mut self, map: &TileMap, x: i32, y: i32) {
+        for index in 0..map.layer_count() {
+            self.draw_tile_layer(map, index, x, y);
+        }
+    }
+
+    // Draws visible tiles of a single layer, e.g. to draw sprites between layers
+    pub fn draw_tile_layer(&mut self, map: &TileMap, index: usize, x: i32, y: i32) {
+        let Some(layer) = map.layer(index).filter(|layer| layer.is_visible()) else {
+            return;
+        };
+
+        let (tile_width, tile_height) = (map.tile_width(), map.tile_height());
+
+        // Area of map that can reach target, in map pixels
+        let visible = match self.camera() {
+            Some(camera) => camera.visible_rect((self.width(), self.height())),
+            None => self.clip_bounds(),
+        };
+
+        let (map_width, map_height) = map.pixel_size();
+        let bounds = Rect::new(x, y, map_width, map_height);
+
+        let Some(visible) = visible.intersection(&bounds) else {
+            return;
+        };
+
+        let first_column = (visible.x - x) as u32 / tile_width;
+        let first_row = (visible.y - y) as u32 / tile_height;
+        let last_column = (visible.right() - x - 1) as u32 / tile_width;
+        let last_row = (visible.bottom() - y - 1) as u32 / tile_height;
+
+        let tile_rect = |column: u32, row: u32| {
+            Rect::new(
+                x + (column * tile_width) as i32,
+                y + (row * tile_height) as i32,
+                tile_width,
+                tile_height,
+            )
+        };
+
+        if !layer.is_static() {
+            for row in first_row..=last_row {
+                for column in first_column..=last_column {
+                    if let Some(tile) = layer.tile(column, row) {
+                        self.draw_tile(map, tile, tile_rect(column, row));
+                    }
+                }
+            }
+
+            return;
+        }
+
+        for chunk_row in first_row / Chunk::SIZE..=last_row / Chunk::SIZE {
+            for chunk_column in first_column / Chunk::SIZE..=last_column / Chunk::SIZE {
+                let chunk = layer.chunk(chunk_column, chunk_row, || {
+                    Chunk::build(map, layer, chunk_column, chunk_row)
+                });
+
+                if let Some(canvas) = &chunk.canvas {
+                    let origin = tile_rect(chunk_column * Chunk::SIZE, chunk_row * Chunk::SIZE);
+                    let source = Rect::new(0, 0, canvas.width, canvas.height);
+                    let destination = Rect::new(origin.x, origin.y, canvas.width, canvas.height);
+
+                    self.draw_region_of(canvas, source, destination);
+                }
+
+                for &(column, row) in &chunk.animated {
+                    let is_visible = (first_column..=last_column).contains(&column)
+                        && (first_row..=last_row).contains(&row);
+
+                    if let Some(tile) = layer.tile(column, row).filter(|_| is_visible) {
+                        self.draw_tile(map, tile, tile_rect(column, row));
+                    }
+                }
+            }
+        }
+    }
+
+    // Draws one tile in its current animation frame
+    fn draw_tile(&mut self, map: &TileMap, tile: Tile, destination: Rect) {
+        let Some((tileset, frame)) = map.tile_source(map.displayed_index(&tile)) else {
+            return;
+        };
+
+        let source = Rect::new(0, 0, frame.width, frame.height);
+
+        self.draw_region_of(
+            &TileSource {
+                tileset,
+                frame,
+                tile,
+            },
+            source,
+            destination,
+        );
+    }
+}
@@ -0,0 +1,104 @@
+use std::{
+    error::Error,
+    fmt::{Display, Formatter, Result},
+};
+
+#[derive(Debug)]
+pub enum SnapshotError {
+    RenderFailed {
+        name: String,
+        source: Box<dyn Error>,
+    },
+    GoldenMissing {
+        name: String,
+        path: String,
+    },
+    GoldenRead {
+        path: String,
+        source: image::ImageError,
+    },
+    ImageWrite {
+        path: String,
+        source: image::ImageError,
+    },
+    SizeMismatch {
+        name: String,
+        expected_width: u32,
+        expected_height: u32,
+        actual_width: u32,
+        actual_height: u32,
+    },
+    PixelMismatch {
+        name: String,
+        mismatched_pixels: usize,
+        diff_path: String,
+    },
+}
+
+impl Display for SnapshotError {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> Result {
+        match self {
+            SnapshotError::RenderFailed { name, source } => {
+                write!(
+                    formatter,
+                    "Failed to render snapshot '{}': {}",
+                    name, source
+                )
+            }
+            SnapshotError::GoldenMissing { name, path } => {
+                write!(
+                    formatter,
+                    "Golden image for '{}' not found at '{}' (set {}=1 to create it)",
+                    name,
+                    path,
+                    super::BLESS_VARIABLE
+                )
+            }
+            SnapshotError::GoldenRead { path, source } => {
+                write!(
+                    formatter,
+                    "Failed to read golden image '{}': {}",
+                    path, source
+                )
+            }
+            SnapshotError::ImageWrite { path, source } => {
+                write!(formatter, "Failed to write image '{}': {}", path, source)
+            }
+            SnapshotError::SizeMismatch {
+                name,
+                expected_width,
+                expected_height,
+                actual_width,
+                actual_height,
+            } => {
+                write!(
+                    formatter,
+                    "Snapshot '{}' size mismatch: expected {}x{}, got {}x{}",
+                    name, expected_width, expected_height, actual_width, actual_height
+                )
+            }
+            SnapshotError::PixelMismatch {
+                name,
+                mismatched_pixels,
+                diff_path,
+            } => {
+                write!(
+                    formatter,
+                    "Snapshot '{}' differs in {} pixels (diff written to '{}')",
+                    name, mismatched_pixels, diff_path
+                )
+            }
+        }
+    }
+}
+
+impl Error for SnapshotError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            SnapshotError::RenderFailed { source, .. } => Some(source.as_ref()),
+            SnapshotError::GoldenRead { source, .. } => Some(source),
+            SnapshotError::ImageWrite { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,7 @@
+mod error;
+mod snapshot;
+
+pub use error::SnapshotError;
+pub use snapshot::{
+    compare_snapshot, render_frame, snapshot_application, SnapshotConfiguration, BLESS_VARIABLE,
+};
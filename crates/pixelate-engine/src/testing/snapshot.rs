@@ -0,0 +1,347 @@
+use super::SnapshotError;
+use crate::{
+    application::Application,
+    log_info, log_warn,
+    renderer::Renderer,
+    window::{Window, WindowConfiguration},
+};
+use image::{save_buffer, ExtendedColorType, ImageError};
+use std::{
+    env,
+    error::Error,
+    fs::{create_dir_all, remove_file},
+    path::Path,
+};
+
+// Environment variable that overwrites golden images instead of comparing
+pub const BLESS_VARIABLE: &str = "PIXELATE_BLESS";
+
+pub struct SnapshotConfiguration {
+    pub directory: String,
+    pub tolerance: u8,
+    pub max_mismatched_pixels: usize,
+}
+
+impl Default for SnapshotConfiguration {
+    // Creates a configuration that stores goldens in tests/golden and requires exact matches
+    fn default() -> Self {
+        Self {
+            directory: "tests/golden".to_string(),
+            tolerance: 0,
+            max_mismatched_pixels: 0,
+        }
+    }
+}
+
+// Renders a single frame of an application into an offscreen renderer
+pub fn render_frame<A: Application>(
+    app: &mut A,
+    width: u32,
+    height: u32,
+) -> Result<Renderer, Box<dyn Error>> {
+    let window = Window::headless(&WindowConfiguration {
+        width,
+        height,
+        ..Default::default()
+    });
+
+    let mut renderer = Renderer::new(&window)?;
+    app.on_init(&window)?;
+    app.on_render(&window, &mut renderer);
+    renderer.apply_post_effects();
+    app.on_shutdown(&window);
+
+    Ok(renderer)
+}
+
+// Renders an application frame and compares it against its golden image
+pub fn snapshot_application<A: Application>(
+    app: &mut A,
+    width: u32,
+    height: u32,
+    name: &str,
+    config: &SnapshotConfiguration,
+) -> Result<(), SnapshotError> {
+    let renderer =
+        render_frame(app, width, height).map_err(|source| SnapshotError::RenderFailed {
+            name: name.to_string(),
+            source,
+        })?;
+
+    compare_snapshot(&renderer, name, config)
+}
+
+// Compares renderer contents against golden image, blessing it when requested
+pub fn compare_snapshot(
+    renderer: &Renderer,
+    name: &str,
+    config: &SnapshotConfiguration,
+) -> Result<(), SnapshotError> {
+    compare_or_bless(renderer, name, config, is_blessing())
+}
+
+// Compares renderer contents against golden image, or overwrites golden when blessing
+fn compare_or_bless(
+    renderer: &Renderer,
+    name: &str,
+    config: &SnapshotConfiguration,
+    is_blessing: bool,
+) -> Result<(), SnapshotError> {
+    let directory = Path::new(&config.directory);
+    let golden_path = directory.join(format!("{}.png", name));
+    let actual_path = directory.join(format!("{}.actual.png", name));
+    let diff_path = directory.join(format!("{}.diff.png", name));
+
+    let (width, height) = (renderer.width(), renderer.height());
+    let actual_bytes = renderer.to_rgba_bytes();
+
+    if is_blessing {
+        write_png(&golden_path, &actual_bytes, width, height)?;
+        log_info!(
+            "Snapshot",
+            "Blessed golden image '{}'",
+            golden_path.display()
+        );
+
+        return Ok(());
+    }
+
+    if !golden_path.exists() {
+        write_png(&actual_path, &actual_bytes, width, height)?;
+
+        return Err(SnapshotError::GoldenMissing {
+            name: name.to_string(),
+            path: golden_path.display().to_string(),
+        });
+    }
+
+    let golden = image::open(&golden_path)
+        .map_err(|source| SnapshotError::GoldenRead {
+            path: golden_path.display().to_string(),
+            source,
+        })?
+        .into_rgba8();
+
+    if golden.dimensions() != (width, height) {
+        write_png(&actual_path, &actual_bytes, width, height)?;
+
+        return Err(SnapshotError::SizeMismatch {
+            name: name.to_string(),
+            expected_width: golden.width(),
+            expected_height: golden.height(),
+            actual_width: width,
+            actual_height: height,
+        });
+    }
+
+    let (mismatched_pixels, diff_bytes) =
+        diff_images(golden.as_raw(), &actual_bytes, config.tolerance);
+
+    if mismatched_pixels > config.max_mismatched_pixels {
+        write_png(&actual_path, &actual_bytes, width, height)?;
+        write_png(&diff_path, &diff_bytes, width, height)?;
+
+        return Err(SnapshotError::PixelMismatch {
+            name: name.to_string(),
+            mismatched_pixels,
+            diff_path: diff_path.display().to_string(),
+        });
+    }
+
+    if mismatched_pixels > 0 {
+        log_warn!(
+            "Snapshot",
+            "Snapshot '{}' differs in {} pixels, within allowed limit",
+            name,
+            mismatched_pixels
+        );
+    }
+
+    // Leftovers from a previous failed run would be misleading now
+    let _ = remove_file(&actual_path);
+    let _ = remove_file(&diff_path);
+
+    Ok(())
+}
+
+// Checks if golden images should be overwritten by this run
+fn is_blessing() -> bool {
+    env::var(BLESS_VARIABLE).is_ok_and(|value| !value.is_empty() && value != "0")
+}
+
+// Counts pixels differing beyond tolerance and builds a highlighted diff image
+fn diff_images(golden_bytes: &[u8], actual_bytes: &[u8], tolerance: u8) -> (usize, Vec<u8>) {
+    let mut mismatched_pixels = 0;
+    let mut diff_bytes = Vec::with_capacity(actual_bytes.len());
+
+    for (golden, actual) in golden_bytes
+        .chunks_exact(4)
+        .zip(actual_bytes.chunks_exact(4))
+    {
+        let is_mismatch = golden
+            .iter()
+            .zip(actual)
+            .any(|(&expected, &found)| expected.abs_diff(found) > tolerance);
+
+        if is_mismatch {
+            mismatched_pixels += 1;
+            diff_bytes.extend_from_slice(&[255, 0, 0, 255]);
+        } else {
+            // Matching pixels are dimmed to grayscale so mismatches stand out
+            let luminance =
+                (golden[0] as u32 * 77 + golden[1] as u32 * 150 + golden[2] as u32 * 29) >> 8;
+            let dimmed = (luminance / 3) as u8;
+            diff_bytes.extend_from_slice(&[dimmed, dimmed, dimmed, 255]);
+        }
+    }
+
+    (mismatched_pixels, diff_bytes)
+}
+
+// Writes RGBA bytes as a PNG, creating parent directories as needed
+fn write_png(path: &Path, rgba_bytes: &[u8], width: u32, height: u32) -> Result<(), SnapshotError> {
+    let to_error = |source| SnapshotError::ImageWrite {
+        path: path.display().to_string(),
+        source,
+    };
+
+    if let Some(parent) = path.parent() {
+        create_dir_all(parent).map_err(|error| to_error(ImageError::IoError(error)))?;
+    }
+
+    save_buffer(path, rgba_bytes, width, height, ExtendedColorType::Rgba8).map_err(to_error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{env, fs, path::PathBuf};
+
+    // Creates an empty directory unique to this test process
+    fn scratch_directory(name: &str) -> PathBuf {
+        let directory =
+            env::temp_dir().join(format!("pixelate-snapshot-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&directory);
+        directory
+    }
+
+    fn config(
+        directory: &Path,
+        tolerance: u8,
+        max_mismatched_pixels: usize,
+    ) -> SnapshotConfiguration {
+        SnapshotConfiguration {
+            directory: directory.display().to_string(),
+            tolerance,
+            max_mismatched_pixels,
+        }
+    }
+
+    fn filled(color: [u8; 4]) -> Renderer {
+        let mut renderer = Renderer::offscreen(4, 4);
+        renderer.clear(color);
+        renderer
+    }
+
+    #[test]
+    fn diff_images_counts_pixels_beyond_tolerance() {
+        let golden = [10, 20, 30, 255, 10, 20, 30, 255, 10, 20, 30, 255];
+        let actual = [10, 20, 30, 255, 12, 20, 30, 255, 10, 20, 40, 255];
+
+        assert_eq!(diff_images(&golden, &actual, 0).0, 2);
+        assert_eq!(diff_images(&golden, &actual, 2).0, 1);
+        assert_eq!(diff_images(&golden, &actual, 10).0, 0);
+    }
+
+    #[test]
+    fn diff_images_marks_mismatches_red_and_dims_matches() {
+        let golden = [255, 255, 255, 255, 0, 0, 0, 255];
+        let actual = [255, 255, 255, 255, 0, 255, 0, 255];
+
+        let (mismatched_pixels, diff_bytes) = diff_images(&golden, &actual, 0);
+
+        assert_eq!(mismatched_pixels, 1);
+        assert_eq!(diff_bytes, vec![85, 85, 85, 255, 255, 0, 0, 255]);
+    }
+
+    #[test]
+    fn blessing_writes_golden_that_later_matches() {
+        let directory = scratch_directory("bless");
+        let config = config(&directory, 0, 0);
+        let renderer = filled([40, 80, 120, 255]);
+
+        assert!(matches!(
+            compare_or_bless(&renderer, "frame", &config, false),
+            Err(SnapshotError::GoldenMissing { .. })
+        ));
+        assert!(directory.join("frame.actual.png").exists());
+
+        compare_or_bless(&renderer, "frame", &config, true).unwrap();
+        assert!(directory.join("frame.png").exists());
+
+        compare_or_bless(&renderer, "frame", &config, false).unwrap();
+        assert!(!directory.join("frame.actual.png").exists());
+
+        let _ = fs::remove_dir_all(&directory);
+    }
+
+    #[test]
+    fn mismatch_writes_diff_image_unless_within_limits() {
+        let directory = scratch_directory("mismatch");
+        compare_or_bless(
+            &filled([40, 80, 120, 255]),
+            "frame",
+            &config(&directory, 0, 0),
+            true,
+        )
+        .unwrap();
+
+        let mut renderer = filled([40, 80, 120, 255]);
+        renderer.draw_point(1, 1, [44, 80, 120, 255]);
+
+        let result = compare_or_bless(&renderer, "frame", &config(&directory, 0, 0), false);
+        assert!(matches!(
+            result,
+            Err(SnapshotError::PixelMismatch {
+                mismatched_pixels: 1,
+                ..
+            })
+        ));
+
+        let diff = image::open(directory.join("frame.diff.png"))
+            .unwrap()
+            .into_rgba8();
+        assert_eq!(diff.get_pixel(1, 1).0, [255, 0, 0, 255]);
+
+        compare_or_bless(&renderer, "frame", &config(&directory, 4, 0), false).unwrap();
+        compare_or_bless(&renderer, "frame", &config(&directory, 0, 1), false).unwrap();
+        assert!(!directory.join("frame.diff.png").exists());
+
+        let _ = fs::remove_dir_all(&directory);
+    }
+
+    #[test]
+    fn size_mismatch_is_reported() {
+        let directory = scratch_directory("size");
+        compare_or_bless(
+            &filled([0, 0, 0, 255]),
+            "frame",
+            &config(&directory, 0, 0),
+            true,
+        )
+        .unwrap();
+
+        let renderer = Renderer::offscreen(2, 2);
+
+        assert!(matches!(
+            compare_or_bless(&renderer, "frame", &config(&directory, 0, 0), false),
+            Err(SnapshotError::SizeMismatch {
+                expected_width: 4,
+                actual_width: 2,
+                ..
+            })
+        ));
+
+        let _ = fs::remove_dir_all(&directory);
+    }
+}
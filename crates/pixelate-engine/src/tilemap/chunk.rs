@@ -0,0 +1,107 @@
+use super::{TileLayer, TileMap};
+use crate::{assets::ImageData, renderer::Canvas};
+
+// Pre-rendered block of a static layer; animated tiles are left out and drawn on top every frame
+#[derive(Clone)]
+pub(crate) struct Chunk {
+    pub(crate) canvas: Option<Canvas>,
+    pub(crate) animated: Vec<(u32, u32)>,
+}
+
+impl Chunk {
+    // Width and height of a chunk in tiles
+    pub(crate) const SIZE: u32 = 16;
+
+    // Renders all non-animated tiles of a chunk into a canvas
+    pub(crate) fn build(
+        map: &TileMap,
+        layer: &TileLayer,
+        chunk_column: u32,
+        chunk_row: u32,
+    ) -> Self {
+        let (tile_width, tile_height) = (map.tile_width(), map.tile_height());
+        let first_column = chunk_column * Self::SIZE;
+        let first_row = chunk_row * Self::SIZE;
+        let columns = Self::SIZE.min(layer.columns().saturating_sub(first_column));
+        let rows = Self::SIZE.min(layer.rows().saturating_sub(first_row));
+
+        let width = columns * tile_width;
+        let mut rgba_bytes = vec![0; (width * rows * tile_height * 4) as usize];
+        let mut animated = Vec::new();
+        let mut is_empty = true;
+
+        for row in first_row..first_row + rows {
+            for column in first_column..first_column + columns {
+                let Some(tile) = layer.tile(column, row) else {
+                    continue;
+                };
+
+                if map.animation(tile.index).is_some() {
+                    animated.push((column, row));
+                    continue;
+                }
+
+                let origin_x = (column - first_column) * tile_width;
+                let origin_y = (row - first_row) * tile_height;
+
+                for y in 0..tile_height {
+                    for x in 0..tile_width {
+                        let Some(rgba) = map.tile_pixel(&tile, tile.index, x, y) else {
+                            continue;
+                        };
+
+                        let index = (((origin_y + y) * width + origin_x + x) * 4) as usize;
+                        rgba_bytes[index..index + 4].copy_from_slice(&rgba);
+                        is_empty &= rgba[3] == 0;
+                    }
+                }
+            }
+        }
+
+        let canvas = (!is_empty).then(|| {
+            Canvas::from_image(&ImageData::from_bytes(
+                rgba_bytes,
+                width,
+                rows * tile_height,
+            ))
+        });
+
+        Self { canvas, animated }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        renderer::Renderer,
+        tilemap::{Tile, TileAnimation},
+    };
+
+    #[test]
+    fn animated_tiles_are_left_out_of_static_chunks_and_keep_playing() {
+        let red_and_blue = vec![255, 0, 0, 255, 0, 0, 255, 255];
+        let tileset = ImageData::from_bytes(red_and_blue, 1, 2);
+        let mut map = TileMap::new(tileset, 1, 1, 2, 1).unwrap();
+        map.set_animation(1, TileAnimation::uniform(&[1, 0], 1.0).unwrap());
+
+        let layer = map.add_layer("water");
+        let target = map.layer_mut(layer).unwrap();
+        target.set_tile(0, 0, Some(Tile::new(0)));
+        target.set_tile(1, 0, Some(Tile::new(1)));
+
+        let chunk = Chunk::build(&map, map.layer(layer).unwrap(), 0, 0);
+        assert_eq!(chunk.animated, vec![(1, 0)]);
+        assert_eq!(chunk.canvas.as_ref().unwrap().pixels()[1], 0);
+
+        let mut renderer = Renderer::offscreen(2, 1);
+        renderer.draw_tile_map(&map, 0, 0);
+        assert_eq!(renderer.pixel(1, 0), Some([0, 0, 255, 255]));
+
+        // Cached chunk stays, while animated tile is drawn in its next frame
+        map.update(1.0);
+        renderer.draw_tile_map(&map, 0, 0);
+        assert_eq!(renderer.pixel(0, 0), Some([255, 0, 0, 255]));
+        assert_eq!(renderer.pixel(1, 0), Some([255, 0, 0, 255]));
+    }
+}
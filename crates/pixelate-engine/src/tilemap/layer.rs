@@ -0,0 +1,206 @@
+use super::{chunk::Chunk, Tile};
+use crate::log_warn;
+use std::{
+    cell::{RefCell, RefMut},
+    collections::HashMap,
+};
+
+#[derive(Clone)]
+pub struct TileLayer {
+    name: String,
+    columns: u32,
+    rows: u32,
+    tiles: Vec<Option<Tile>>,
+    visible: bool,
+    is_static: bool,
+    square_tiles: bool,
+    chunks: RefCell<HashMap<(u32, u32), Chunk>>,
+}
+
+impl TileLayer {
+    // Creates an empty, visible and static layer of given size in tiles; square_tiles tells if
+    // map tiles can be flipped diagonally
+    pub(crate) fn new(name: &str, columns: u32, rows: u32, square_tiles: bool) -> Self {
+        Self {
+            name: name.to_string(),
+            columns,
+            rows,
+            tiles: vec![None; (columns * rows) as usize],
+            visible: true,
+            is_static: true,
+            square_tiles,
+            chunks: RefCell::new(HashMap::new()),
+        }
+    }
+
+    // Returns name layer was created with
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    // Returns width of layer in tiles
+    pub fn columns(&self) -> u32 {
+        self.columns
+    }
+
+    // Returns height of layer in tiles
+    pub fn rows(&self) -> u32 {
+        self.rows
+    }
+
+    // Returns tile at given column and row, None if empty or outside layer
+    pub fn tile(&self, column: u32, row: u32) -> Option<Tile> {
+        if column >= self.columns || row >= self.rows {
+            return None;
+        }
+
+        self.tiles[(row * self.columns + column) as usize]
+    }
+
+    // Places or removes a tile at given column and row, rebuilding its cached chunk on next draw;
+    // diagonally flipped tiles are ignored unless map tiles are square
+    pub fn set_tile(&mut self, column: u32, row: u32, tile: Option<Tile>) {
+        if column >= self.columns || row >= self.rows || !self.accepts(tile) {
+            return;
+        }
+
+        let index = (row * self.columns + column) as usize;
+
+        if self.tiles[index] == tile {
+            return;
+        }
+
+        self.tiles[index] = tile;
+        self.chunks
+            .get_mut()
+            .remove(&(column / Chunk::SIZE, row / Chunk::SIZE));
+    }
+
+    // Sets every tile of layer to same tile or clears it
+    pub fn fill(&mut self, tile: Option<Tile>) {
+        if !self.accepts(tile) {
+            return;
+        }
+
+        self.tiles.fill(tile);
+        self.invalidate();
+    }
+
+    // Checks if layer is drawn
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    // Shows or hides layer
+    pub fn set_visible(&mut self, visible: bool) {
+        self.visible = visible;
+    }
+
+    // Checks if layer is pre-rendered into cached chunks
+    pub fn is_static(&self) -> bool {
+        self.is_static
+    }
+
+    // Sets if layer is pre-rendered into cached chunks; turn off for layers edited every frame
+    pub fn set_static(&mut self, is_static: bool) {
+        self.is_static = is_static;
+        self.invalidate();
+    }
+
+    // Checks if a tile can be placed; swapping axes of a non-square tile would drop the pixels
+    // that no longer fit, so diagonal flips are rejected for those
+    fn accepts(&self, tile: Option<Tile>) -> bool {
+        if self.square_tiles || !tile.is_some_and(|tile| tile.flip_diagonal) {
+            return true;
+        }
+
+        log_warn!(
+            "Tilemap",
+            "Ignoring diagonally flipped tile on layer '{}': map tiles are not square",
+            self.name
+        );
+
+        false
+    }
+
+    // Drops all cached chunks so they are rebuilt on next draw
+    pub(crate) fn invalidate(&mut self) {
+        self.chunks.get_mut().clear();
+    }
+
+    // Returns cached chunk at given chunk column and row, building it when missing
+    pub(crate) fn chunk(
+        &self,
+        chunk_column: u32,
+        chunk_row: u32,
+        build: impl FnOnce() -> Chunk,
+    ) -> RefMut<'_, Chunk> {
+        RefMut::map(self.chunks.borrow_mut(), |chunks| {
+            chunks
+                .entry((chunk_column, chunk_row))
+                .or_insert_with(build)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        assets::ImageData,
+        renderer::Renderer,
+        tilemap::{Tile, TileMap},
+    };
+
+    const RED: [u8; 4] = [255, 0, 0, 255];
+    const BLUE: [u8; 4] = [0, 0, 255, 255];
+
+    // Creates a map of 1x1 pixel tiles where tile 0 is red and tile 1 is blue
+    fn two_color_map(columns: u32, tile_height: u32) -> TileMap {
+        let pixels = [RED, BLUE]
+            .iter()
+            .flat_map(|&color| [color].repeat(tile_height as usize))
+            .flatten()
+            .collect();
+        let tileset = ImageData::from_bytes(pixels, 1, 2 * tile_height);
+
+        TileMap::new(tileset, 1, tile_height, columns, 1).unwrap()
+    }
+
+    #[test]
+    fn set_tile_rebuilds_only_chunk_it_lands_in() {
+        let mut map = two_color_map(32, 1);
+        let layer = map.add_layer("ground");
+        map.layer_mut(layer).unwrap().fill(Some(Tile::new(0)));
+
+        let mut renderer = Renderer::offscreen(32, 1);
+        renderer.draw_tile_map(&map, 0, 0);
+        assert_eq!(map.layer(layer).unwrap().chunks.borrow().len(), 2);
+
+        map.layer_mut(layer)
+            .unwrap()
+            .set_tile(20, 0, Some(Tile::new(1)));
+
+        let chunks = map.layer(layer).unwrap().chunks.borrow();
+        assert!(chunks.contains_key(&(0, 0)));
+        assert!(!chunks.contains_key(&(1, 0)));
+        drop(chunks);
+
+        renderer.draw_tile_map(&map, 0, 0);
+        assert_eq!(renderer.pixel(19, 0), Some(RED));
+        assert_eq!(renderer.pixel(20, 0), Some(BLUE));
+    }
+
+    #[test]
+    fn diagonal_flips_are_rejected_for_non_square_tiles() {
+        let mut map = two_color_map(2, 2);
+        let layer = map.add_layer("ground");
+        let target = map.layer_mut(layer).unwrap();
+
+        target.set_tile(0, 0, Some(Tile::new(1).flip_diagonal(true)));
+        target.fill(Some(Tile::new(1).flip_diagonal(true)));
+        target.set_tile(1, 0, Some(Tile::new(1).flip_x(true)));
+
+        assert_eq!(target.tile(0, 0), None);
+        assert_eq!(target.tile(1, 0), Some(Tile::new(1).flip_x(true)));
+    }
+}
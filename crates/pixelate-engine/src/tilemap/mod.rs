@@ -0,0 +1,11 @@
+mod chunk;
+mod layer;
+mod object;
+mod tile;
+mod tile_map;
+
+pub(crate) use chunk::Chunk;
+pub use layer::TileLayer;
+pub use object::{MapObject, ObjectShape, Properties, PropertyValue};
+pub use tile::{Tile, TileAnimation};
+pub use tile_map::TileMap;
@@ -0,0 +1,207 @@
+use super::Tile;
+use crate::renderer::Rect;
+use std::collections::HashMap;
+
+// Custom properties by name, as set in a map editor
+pub type Properties = HashMap<String, PropertyValue>;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum PropertyValue {
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    String(String),
+    Color([u8; 4]),
+    File(String),
+    Object(u32),
+    Class(Properties),
+}
+
+impl PropertyValue {
+    // Returns value if it is a bool
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            PropertyValue::Bool(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    // Returns value if it is an integer or an object id
+    pub fn as_int(&self) -> Option<i64> {
+        match self {
+            PropertyValue::Int(value) => Some(*value),
+            PropertyValue::Object(id) => Some(*id as i64),
+            _ => None,
+        }
+    }
+
+    // Returns value if it is a number, converting integers
+    pub fn as_float(&self) -> Option<f64> {
+        match self {
+            PropertyValue::Float(value) => Some(*value),
+            PropertyValue::Int(value) => Some(*value as f64),
+            _ => None,
+        }
+    }
+
+    // Returns value if it is a string or a file path
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            PropertyValue::String(value) | PropertyValue::File(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    // Returns value if it is an RGBA color
+    pub fn as_color(&self) -> Option<[u8; 4]> {
+        match self {
+            PropertyValue::Color(color) => Some(*color),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum ObjectShape {
+    #[default]
+    Rectangle,
+    Ellipse,
+    Point,
+    // Corners relative to object position
+    Polygon(Vec<(f32, f32)>),
+    // Line points relative to object position
+    Polyline(Vec<(f32, f32)>),
+    Text(String),
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MapObject {
+    pub id: u32,
+    pub name: String,
+    pub class: String,
+    pub layer: String,
+    pub position: (f32, f32),
+    pub size: (f32, f32),
+    // Clockwise rotation around position in radians
+    pub rotation: f32,
+    pub visible: bool,
+    pub shape: ObjectShape,
+    // Tile shown by object, which then hangs above its position like in Tiled
+    pub tile: Option<Tile>,
+    pub properties: Properties,
+}
+
+impl MapObject {
+    // Returns custom property with given name
+    pub fn property(&self, name: &str) -> Option<&PropertyValue> {
+        self.properties.get(name)
+    }
+
+    // Returns smallest rectangle of map pixels covering object, at least one pixel wide and tall
+    pub fn bounds(&self) -> Rect {
+        let (sin, cos) = self.rotation.sin_cos();
+
+        let (mut min_x, mut min_y) = (f32::MAX, f32::MAX);
+        let (mut max_x, mut max_y) = (f32::MIN, f32::MIN);
+
+        // Snaps away float noise of right angle rotations, which would otherwise grow bounds a pixel
+        let snap = |value: f32| (value * 1024.0).round() / 1024.0;
+
+        for (local_x, local_y) in self.outline() {
+            let x = snap(self.position.0 + local_x * cos - local_y * sin);
+            let y = snap(self.position.1 + local_x * sin + local_y * cos);
+
+            min_x = min_x.min(x);
+            min_y = min_y.min(y);
+            max_x = max_x.max(x);
+            max_y = max_y.max(y);
+        }
+
+        let (left, top) = (min_x.floor() as i32, min_y.floor() as i32);
+
+        Rect::new(
+            left,
+            top,
+            (max_x.ceil() as i32 - left).max(1) as u32,
+            (max_y.ceil() as i32 - top).max(1) as u32,
+        )
+    }
+
+    // Checks if a point in map pixels lies inside object; points and polylines contain nothing
+    pub fn contains(&self, x: f32, y: f32) -> bool {
+        let (sin, cos) = self.rotation.sin_cos();
+        let (delta_x, delta_y) = (x - self.position.0, y - self.position.1);
+
+        // Point in object space, undoing rotation
+        let local_x = delta_x * cos + delta_y * sin;
+        let local_y = delta_y * cos - delta_x * sin;
+
+        let (width, height) = self.size;
+        let top = if self.tile.is_some() { -height } else { 0.0 };
+
+        match &self.shape {
+            ObjectShape::Rectangle | ObjectShape::Text(_) => {
+                local_x >= 0.0 && local_x < width && local_y >= top && local_y < top + height
+            }
+            ObjectShape::Ellipse => {
+                if width <= 0.0 || height <= 0.0 {
+                    return false;
+                }
+
+                let normal_x = (local_x - width / 2.0) / (width / 2.0);
+                let normal_y = (local_y - top - height / 2.0) / (height / 2.0);
+
+                normal_x * normal_x + normal_y * normal_y <= 1.0
+            }
+            ObjectShape::Polygon(points) => polygon_contains(points, local_x, local_y),
+            ObjectShape::Point | ObjectShape::Polyline(_) => false,
+        }
+    }
+
+    // Checks if object bounds overlap a rectangle of map pixels
+    pub fn overlaps(&self, area: &Rect) -> bool {
+        self.bounds().intersection(area).is_some()
+    }
+
+    // Returns points outlining object relative to its position, before rotation
+    fn outline(&self) -> Vec<(f32, f32)> {
+        let (width, height) = self.size;
+        let top = if self.tile.is_some() { -height } else { 0.0 };
+
+        match &self.shape {
+            ObjectShape::Rectangle | ObjectShape::Ellipse | ObjectShape::Text(_) => vec![
+                (0.0, top),
+                (width, top),
+                (0.0, top + height),
+                (width, top + height),
+            ],
+            ObjectShape::Polygon(points) | ObjectShape::Polyline(points) if !points.is_empty() => {
+                points.clone()
+            }
+            _ => vec![(0.0, 0.0)],
+        }
+    }
+}
+
+// Tests a point against a polygon using even-odd rule
+fn polygon_contains(points: &[(f32, f32)], x: f32, y: f32) -> bool {
+    let mut is_inside = false;
+    let mut previous = match points.last() {
+        Some(&point) => point,
+        None => return false,
+    };
+
+    for &(point_x, point_y) in points {
+        let (previous_x, previous_y) = previous;
+
+        if (point_y > y) != (previous_y > y)
+            && x < (previous_x - point_x) * (y - point_y) / (previous_y - point_y) + point_x
+        {
+            is_inside = !is_inside;
+        }
+
+        previous = (point_x, point_y);
+    }
+
+    is_inside
+}
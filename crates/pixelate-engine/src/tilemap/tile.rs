@@ -0,0 +1,121 @@
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Tile {
+    pub index: u32,
+    pub flip_x: bool,
+    pub flip_y: bool,
+    pub flip_diagonal: bool,
+}
+
+impl Tile {
+    // Creates an unflipped tile showing given tileset index
+    pub const fn new(index: u32) -> Self {
+        Self {
+            index,
+            flip_x: false,
+            flip_y: false,
+            flip_diagonal: false,
+        }
+    }
+
+    // Mirrors tile horizontally
+    pub const fn flip_x(mut self, flip_x: bool) -> Self {
+        self.flip_x = flip_x;
+        self
+    }
+
+    // Mirrors tile vertically
+    pub const fn flip_y(mut self, flip_y: bool) -> Self {
+        self.flip_y = flip_y;
+        self
+    }
+
+    // Swaps tile x and y axes, applied before other flips; combine with them for quarter turns.
+    // Only square tiles can be flipped this way, layers of other maps ignore such tiles
+    pub const fn flip_diagonal(mut self, flip_diagonal: bool) -> Self {
+        self.flip_diagonal = flip_diagonal;
+        self
+    }
+
+    // Maps a pixel inside a drawn tile to pixel of tileset frame it shows, None if outside frame
+    pub(crate) fn source_offset(
+        &self,
+        x: u32,
+        y: u32,
+        width: u32,
+        height: u32,
+    ) -> Option<(u32, u32)> {
+        let x = if self.flip_x {
+            width.checked_sub(x + 1)?
+        } else {
+            x
+        };
+        let y = if self.flip_y {
+            height.checked_sub(y + 1)?
+        } else {
+            y
+        };
+
+        // Diagonal flip only maps whole tiles onto themselves when they are square; layers
+        // never hold such tiles otherwise, this check only guards other callers
+        let (x, y) = match self.flip_diagonal {
+            true => (y, x),
+            false => (x, y),
+        };
+
+        (x < width && y < height).then_some((x, y))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct TileAnimation {
+    frames: Vec<(u32, f32)>,
+    duration: f32,
+}
+
+impl TileAnimation {
+    // Creates an animation from tileset indices paired with their duration in seconds
+    pub fn new(frames: Vec<(u32, f32)>) -> Option<Self> {
+        let duration = frames.iter().map(|&(_, duration)| duration.max(0.0)).sum();
+
+        if frames.is_empty() || duration <= 0.0 {
+            return None;
+        }
+
+        Some(Self { frames, duration })
+    }
+
+    // Creates an animation showing each tileset index for same number of seconds
+    pub fn uniform(indices: &[u32], frame_duration: f32) -> Option<Self> {
+        Self::new(
+            indices
+                .iter()
+                .map(|&index| (index, frame_duration))
+                .collect(),
+        )
+    }
+
+    // Returns tileset indices paired with their duration in seconds
+    pub fn frames(&self) -> &[(u32, f32)] {
+        &self.frames
+    }
+
+    // Returns length of one loop in seconds
+    pub fn duration(&self) -> f32 {
+        self.duration
+    }
+
+    // Returns tileset index shown at given time, looping forever
+    pub fn frame_at(&self, time: f32) -> u32 {
+        let mut remaining = time.rem_euclid(self.duration);
+
+        for &(index, duration) in &self.frames {
+            if remaining < duration {
+                return index;
+            }
+
+            remaining -= duration.max(0.0);
+        }
+
+        self.frames[self.frames.len() - 1].0
+    }
+}
@@ -0,0 +1,290 @@
+use super::{MapObject, Properties, PropertyValue, Tile, TileAnimation, TileLayer};
+use crate::{
+    assets::{ImageData, SpriteSheet},
+    renderer::Rect,
+};
+use std::collections::HashMap;
+
+#[derive(Clone)]
+pub struct TileMap {
+    tileset: SpriteSheet,
+    // Tiles with an image of their own, indexed after tileset tiles
+    tile_images: Vec<ImageData>,
+    columns: u32,
+    rows: u32,
+    layers: Vec<TileLayer>,
+    animations: HashMap<u32, TileAnimation>,
+    time: f32,
+    objects: Vec<MapObject>,
+    properties: Properties,
+}
+
+impl TileMap {
+    // Creates a map without layers; tileset is sliced into tiles read left-to-right, top-to-bottom
+    pub fn new(
+        tileset: ImageData,
+        tile_width: u32,
+        tile_height: u32,
+        columns: u32,
+        rows: u32,
+    ) -> Option<Self> {
+        Some(Self {
+            tileset: SpriteSheet::new(tileset, tile_width, tile_height)?,
+            tile_images: Vec::new(),
+            columns,
+            rows,
+            layers: Vec::new(),
+            animations: HashMap::new(),
+            time: 0.0,
+            objects: Vec::new(),
+            properties: Properties::new(),
+        })
+    }
+
+    // Returns image tiles are taken from
+    pub fn tileset(&self) -> &ImageData {
+        self.tileset.image()
+    }
+
+    // Returns number of tiles in tileset
+    pub fn tile_count(&self) -> usize {
+        self.tileset.frame_count()
+    }
+
+    // Adds a tile with its own image of any size, e.g. a tree placed as an object, returning
+    // its index; such tiles follow tileset tiles in index order
+    pub fn add_tile_image(&mut self, image: ImageData) -> u32 {
+        self.tile_images.push(image);
+
+        (self.tile_count() + self.tile_images.len() - 1) as u32
+    }
+
+    // Returns width of a tile in pixels
+    pub fn tile_width(&self) -> u32 {
+        self.tileset.frame_width()
+    }
+
+    // Returns height of a tile in pixels
+    pub fn tile_height(&self) -> u32 {
+        self.tileset.frame_height()
+    }
+
+    // Returns width of map in tiles
+    pub fn columns(&self) -> u32 {
+        self.columns
+    }
+
+    // Returns height of map in tiles
+    pub fn rows(&self) -> u32 {
+        self.rows
+    }
+
+    // Returns size of map in pixels
+    pub fn pixel_size(&self) -> (u32, u32) {
+        (
+            self.columns * self.tile_width(),
+            self.rows * self.tile_height(),
+        )
+    }
+
+    // Adds an empty layer drawn above existing ones, returning its index
+    pub fn add_layer(&mut self, name: &str) -> usize {
+        let square_tiles = self.tile_width() == self.tile_height();
+
+        self.layers
+            .push(TileLayer::new(name, self.columns, self.rows, square_tiles));
+
+        self.layers.len() - 1
+    }
+
+    // Returns layer at given index, counted from bottom
+    pub fn layer(&self, index: usize) -> Option<&TileLayer> {
+        self.layers.get(index)
+    }
+
+    // Returns layer at given index for editing
+    pub fn layer_mut(&mut self, index: usize) -> Option<&mut TileLayer> {
+        self.layers.get_mut(index)
+    }
+
+    // Returns first layer with given name
+    pub fn layer_by_name(&self, name: &str) -> Option<&TileLayer> {
+        self.layers.iter().find(|layer| layer.name() == name)
+    }
+
+    // Returns first layer with given name for editing
+    pub fn layer_by_name_mut(&mut self, name: &str) -> Option<&mut TileLayer> {
+        self.layers.iter_mut().find(|layer| layer.name() == name)
+    }
+
+    // Returns all layers from bottom to top
+    pub fn layers(&self) -> &[TileLayer] {
+        &self.layers
+    }
+
+    // Returns number of layers
+    pub fn layer_count(&self) -> usize {
+        self.layers.len()
+    }
+
+    // Makes every tile with given tileset index play an animation
+    pub fn set_animation(&mut self, index: u32, animation: TileAnimation) {
+        self.animations.insert(index, animation);
+        self.invalidate();
+    }
+
+    // Stops animating tiles with given tileset index
+    pub fn remove_animation(&mut self, index: u32) -> Option<TileAnimation> {
+        let animation = self.animations.remove(&index);
+
+        if animation.is_some() {
+            self.invalidate();
+        }
+
+        animation
+    }
+
+    // Returns animation played by tiles with given tileset index
+    pub fn animation(&self, index: u32) -> Option<&TileAnimation> {
+        self.animations.get(&index)
+    }
+
+    // Advances tile animations by elapsed seconds
+    pub fn update(&mut self, delta_time: f32) {
+        self.time += delta_time;
+    }
+
+    // Returns seconds tile animations have been running
+    pub fn time(&self) -> f32 {
+        self.time
+    }
+
+    // Returns tileset index a tile currently shows, following its animation
+    pub fn displayed_index(&self, tile: &Tile) -> u32 {
+        match self.animations.get(&tile.index) {
+            Some(animation) => animation.frame_at(self.time),
+            None => tile.index,
+        }
+    }
+
+    // Converts a position in map pixels to column and row of tile under it
+    pub fn tile_at(&self, x: f32, y: f32) -> Option<(u32, u32)> {
+        if x < 0.0 || y < 0.0 {
+            return None;
+        }
+
+        let column = x as u32 / self.tile_width();
+        let row = y as u32 / self.tile_height();
+
+        (column < self.columns && row < self.rows).then_some((column, row))
+    }
+
+    // Adds an object such as a spawn point, trigger or collision shape
+    pub fn add_object(&mut self, object: MapObject) {
+        self.objects.push(object);
+    }
+
+    // Removes object with given id, returning it
+    pub fn remove_object(&mut self, id: u32) -> Option<MapObject> {
+        let index = self.objects.iter().position(|object| object.id == id)?;
+
+        Some(self.objects.remove(index))
+    }
+
+    // Returns all objects in order they were added
+    pub fn objects(&self) -> &[MapObject] {
+        &self.objects
+    }
+
+    // Returns object with given id
+    pub fn object(&self, id: u32) -> Option<&MapObject> {
+        self.objects.iter().find(|object| object.id == id)
+    }
+
+    // Returns object with given id for editing
+    pub fn object_mut(&mut self, id: u32) -> Option<&mut MapObject> {
+        self.objects.iter_mut().find(|object| object.id == id)
+    }
+
+    // Returns first object with given name
+    pub fn object_by_name(&self, name: &str) -> Option<&MapObject> {
+        self.objects.iter().find(|object| object.name == name)
+    }
+
+    // Returns objects of given class, e.g. every "spawn" or "trigger"
+    pub fn objects_with_class<'a>(&'a self, class: &'a str) -> impl Iterator<Item = &'a MapObject> {
+        self.objects
+            .iter()
+            .filter(move |object| object.class == class)
+    }
+
+    // Returns objects that were placed on object layer with given name
+    pub fn objects_in_layer<'a>(&'a self, layer: &'a str) -> impl Iterator<Item = &'a MapObject> {
+        self.objects
+            .iter()
+            .filter(move |object| object.layer == layer)
+    }
+
+    // Returns objects whose shape contains a point in map pixels
+    pub fn objects_at(&self, x: f32, y: f32) -> impl Iterator<Item = &MapObject> {
+        self.objects
+            .iter()
+            .filter(move |object| object.contains(x, y))
+    }
+
+    // Returns objects whose bounds overlap a rectangle in map pixels
+    pub fn objects_in(&self, area: Rect) -> impl Iterator<Item = &MapObject> {
+        self.objects
+            .iter()
+            .filter(move |object| object.overlaps(&area))
+    }
+
+    // Returns custom properties of whole map
+    pub fn properties(&self) -> &Properties {
+        &self.properties
+    }
+
+    // Returns custom property of whole map with given name
+    pub fn property(&self, name: &str) -> Option<&PropertyValue> {
+        self.properties.get(name)
+    }
+
+    // Sets a custom property of whole map
+    pub fn set_property(&mut self, name: &str, value: PropertyValue) {
+        self.properties.insert(name.to_string(), value);
+    }
+
+    // Drops cached chunks of every layer so they are rebuilt on next draw
+    pub fn invalidate(&mut self) {
+        for layer in &mut self.layers {
+            layer.invalidate();
+        }
+    }
+
+    // Returns source rectangle of tile with given tileset index
+    pub fn tile_frame(&self, index: u32) -> Option<Rect> {
+        self.tileset.frame(index as usize)
+    }
+
+    // Returns image and source rectangle of tile with given index, whether it comes from
+    // tileset or has an image of its own
+    pub fn tile_source(&self, index: u32) -> Option<(&ImageData, Rect)> {
+        if let Some(frame) = self.tile_frame(index) {
+            return Some((self.tileset.image(), frame));
+        }
+
+        let image = self
+            .tile_images
+            .get((index as usize).checked_sub(self.tile_count())?)?;
+
+        Some((image, Rect::new(0, 0, image.width, image.height)))
+    }
+
+    // Returns color of a pixel inside a drawn tile showing given tile index, applying tile flips
+    pub(crate) fn tile_pixel(&self, tile: &Tile, index: u32, x: u32, y: u32) -> Option<[u8; 4]> {
+        let (image, frame) = self.tile_source(index)?;
+        let (offset_x, offset_y) = tile.source_offset(x, y, frame.width, frame.height)?;
+
+        image.pixel(frame.x + offset_x as i32, frame.y + offset_y as i32)
+    }
+}
@@ -1,7 +1,27 @@
+use crate::{input::KeyCode, renderer::RecordingConfiguration};
+use std::path::PathBuf;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RedrawMode {
+    // Renders frames back to back as fast as event loop allows
+    #[default]
+    Continuous,
+    // Sleeps until input arrives or window requests a redraw; delta time spans the wait
+    OnDemand,
+}
+
 pub struct WindowConfiguration {
     pub title: String,
     pub width: u32,
     pub height: u32,
+    pub logical_size: Option<(u32, u32)>,
+    pub border_color: [u8; 4],
+    pub redraw_mode: RedrawMode,
+    pub screenshot_key: Option<KeyCode>,
+    pub screenshot_directory: PathBuf,
+    pub record_key: Option<KeyCode>,
+    pub recording_directory: PathBuf,
+    pub recording: RecordingConfiguration,
 }
 
 impl Default for WindowConfiguration {
@@ -11,6 +31,14 @@ impl Default for WindowConfiguration {
             title: "Untitled".to_string(),
             width: 800,
             height: 800,
+            logical_size: None,
+            border_color: [0, 0, 0, 255],
+            redraw_mode: RedrawMode::Continuous,
+            screenshot_key: Some(KeyCode::F12),
+            screenshot_directory: PathBuf::from("screenshots"),
+            record_key: Some(KeyCode::F10),
+            recording_directory: PathBuf::from("recordings"),
+            recording: RecordingConfiguration::default(),
         }
     }
 }
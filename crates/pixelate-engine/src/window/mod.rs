@@ -2,6 +2,6 @@ mod configuration;
 mod error;
 mod window;
 
-pub use configuration::WindowConfiguration;
+pub use configuration::{RedrawMode, WindowConfiguration};
 pub use error::WindowError;
 pub use window::Window;
@@ -1,6 +1,9 @@
 use super::{configuration::WindowConfiguration, error::WindowError};
 use crate::log_info;
-use std::sync::Arc;
+use std::{
+    cell::{Cell, RefCell},
+    sync::Arc,
+};
 use winit::{
     dpi::LogicalSize,
     event_loop::ActiveEventLoop,
@@ -8,7 +11,16 @@ use winit::{
 };
 
 pub struct Window {
-    inner_window: Arc<WinitWindow>,
+    backend: WindowBackend,
+}
+
+enum WindowBackend {
+    Native(Arc<WinitWindow>),
+    Headless {
+        title: RefCell<String>,
+        size: Cell<(u32, u32)>,
+        redraw_requested: Cell<bool>,
+    },
 }
 
 impl Window {
@@ -42,34 +54,103 @@ impl Window {
         );
 
         Ok(Self {
-            inner_window: Arc::new(window),
+            backend: WindowBackend::Native(Arc::new(window)),
         })
     }
 
-    // Returns a cloned Arc reference to underlying winit window
+    // Creates a window stand-in with no display connection, for tests and CI
+    pub fn headless(config: &WindowConfiguration) -> Self {
+        log_info!(
+            "Window",
+            "Created headless window '{}' ({}x{})",
+            config.title,
+            config.width,
+            config.height
+        );
+
+        Self {
+            backend: WindowBackend::Headless {
+                title: RefCell::new(config.title.to_string()),
+                size: Cell::new((config.width, config.height)),
+                redraw_requested: Cell::new(false),
+            },
+        }
+    }
+
+    // Checks if window has no underlying display window
+    pub fn is_headless(&self) -> bool {
+        matches!(self.backend, WindowBackend::Headless { .. })
+    }
+
+    // Returns a cloned Arc reference to underlying winit window; panics for headless windows
     pub fn inner(&self) -> Arc<WinitWindow> {
-        Arc::clone(&self.inner_window)
+        self.try_inner()
+            .expect("headless window has no underlying winit window")
+    }
+
+    // Returns a cloned Arc reference to underlying winit window, or None for headless windows
+    pub fn try_inner(&self) -> Option<Arc<WinitWindow>> {
+        match &self.backend {
+            WindowBackend::Native(inner_window) => Some(Arc::clone(inner_window)),
+            WindowBackend::Headless { .. } => None,
+        }
     }
 
     // Returns current window title
     pub fn title(&self) -> String {
-        self.inner_window.title()
+        match &self.backend {
+            WindowBackend::Native(inner_window) => inner_window.title(),
+            WindowBackend::Headless { title, .. } => title.borrow().clone(),
+        }
     }
 
     // Sets window title
     pub fn set_title(&self, title: &str) {
-        self.inner_window.set_title(title);
+        match &self.backend {
+            WindowBackend::Native(inner_window) => inner_window.set_title(title),
+            WindowBackend::Headless {
+                title: headless_title,
+                ..
+            } => *headless_title.borrow_mut() = title.to_string(),
+        }
     }
 
     // Returns inner size of window
     pub fn inner_size(&self) -> (u32, u32) {
-        let size = self.inner_window.inner_size();
+        match &self.backend {
+            WindowBackend::Native(inner_window) => {
+                let size = inner_window.inner_size();
+
+                (size.width, size.height)
+            }
+            WindowBackend::Headless { size, .. } => size.get(),
+        }
+    }
 
-        (size.width, size.height)
+    // Internal: Changes reported size of a headless window, as scripted resizes do
+    pub(crate) fn set_headless_size(&self, width: u32, height: u32) {
+        if let WindowBackend::Headless { size, .. } = &self.backend {
+            size.set((width, height));
+        }
     }
 
     // Requests a redraw of window
     pub fn request_redraw(&self) {
-        self.inner_window.request_redraw();
+        match &self.backend {
+            WindowBackend::Native(inner_window) => inner_window.request_redraw(),
+            WindowBackend::Headless {
+                redraw_requested, ..
+            } => redraw_requested.set(true),
+        }
+    }
+
+    // Checks and clears whether a headless window was asked to redraw; native windows report false
+    pub fn take_redraw_request(&self) -> bool {
+        match &self.backend {
+            WindowBackend::Native(_) => false,
+            WindowBackend::Headless {
+                redraw_requested, ..
+            } => redraw_requested.replace(false),
+        }
     }
 }
@@ -0,0 +1,4 @@
+<?xml version="1.0" encoding="UTF-8"?>
+<tileset version="1.10" tiledversion="1.10.2" name="tiles" tilewidth="2" tileheight="2" tilecount="2" columns="2">
+ <image source="tiles.png" width="4" height="2"/>
+</tileset>
@@ -0,0 +1,78 @@
+use pixelate::prelude::*;
+use std::{env, path::PathBuf};
+
+// Remembers window size seen on each resize event
+#[derive(Default)]
+struct SizeProbe {
+    sizes: Vec<(u32, u32)>,
+}
+
+impl Application for SizeProbe {
+    fn on_event(&mut self, window: &Window, event: Event) {
+        if let Event::WindowResized { .. } = event {
+            self.sizes.push(window.inner_size());
+        }
+    }
+}
+
+fn scratch_directory(name: &str) -> PathBuf {
+    env::temp_dir().join(format!("pixelate-{}-{}", name, std::process::id()))
+}
+
+#[test]
+fn scripted_resize_updates_window_and_renderer() {
+    let headless_config = HeadlessConfiguration {
+        frames: 2,
+        script: InputScript::new().event(
+            1,
+            Event::WindowResized {
+                width: 64,
+                height: 48,
+            },
+        ),
+        ..Default::default()
+    };
+
+    let report = Engine::run_headless(
+        SizeProbe::default(),
+        WindowConfiguration::default(),
+        headless_config,
+    )
+    .unwrap();
+
+    assert_eq!(report.app.sizes, vec![(64, 48)]);
+    assert_eq!(
+        (report.renderer.width(), report.renderer.height()),
+        (64, 48)
+    );
+}
+
+#[test]
+fn scripted_hotkeys_do_not_write_files() {
+    let screenshot_directory = scratch_directory("screenshots");
+    let recording_directory = scratch_directory("recordings");
+
+    let window_config = WindowConfiguration {
+        width: 16,
+        height: 16,
+        screenshot_directory: screenshot_directory.clone(),
+        recording_directory: recording_directory.clone(),
+        ..Default::default()
+    };
+
+    let headless_config = HeadlessConfiguration {
+        frames: 6,
+        script: InputScript::new()
+            .key_tap(0, KeyCode::F12)
+            .key_tap(2, KeyCode::F10)
+            .key_tap(4, KeyCode::F10),
+        ..Default::default()
+    };
+
+    let report =
+        Engine::run_headless(SizeProbe::default(), window_config, headless_config).unwrap();
+
+    assert!(!report.renderer.is_recording());
+    assert!(!screenshot_directory.exists());
+    assert!(!recording_directory.exists());
+}
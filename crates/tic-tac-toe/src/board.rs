@@ -0,0 +1,81 @@
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Player {
+    X,
+    O,
+}
+
+impl Player {
+    // Returns player who moves after this one
+    pub fn opponent(self) -> Self {
+        match self {
+            Player::X => Player::O,
+            Player::O => Player::X,
+        }
+    }
+}
+
+// Cell triples that win when held by one player
+const LINES: [[(usize, usize); 3]; 8] = [
+    [(0, 0), (1, 0), (2, 0)],
+    [(0, 1), (1, 1), (2, 1)],
+    [(0, 2), (1, 2), (2, 2)],
+    [(0, 0), (0, 1), (0, 2)],
+    [(1, 0), (1, 1), (1, 2)],
+    [(2, 0), (2, 1), (2, 2)],
+    [(0, 0), (1, 1), (2, 2)],
+    [(2, 0), (1, 1), (0, 2)],
+];
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Board {
+    cells: [[Option<Player>; 3]; 3],
+}
+
+impl Board {
+    // Creates an empty board
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Returns player holding cell at given column and row
+    pub fn cell(&self, column: usize, row: usize) -> Option<Player> {
+        self.cells.get(row)?.get(column).copied().flatten()
+    }
+
+    // Marks an empty cell for player, returning false if cell is taken or outside board
+    pub fn place(&mut self, column: usize, row: usize, player: Player) -> bool {
+        match self
+            .cells
+            .get_mut(row)
+            .and_then(|cells| cells.get_mut(column))
+        {
+            Some(cell @ None) => {
+                *cell = Some(player);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    // Returns player holding a full row, column or diagonal, with cells of that line
+    pub fn winning_line(&self) -> Option<(Player, [(usize, usize); 3])> {
+        LINES.iter().find_map(|&line| {
+            let [first, second, third] = line.map(|(column, row)| self.cell(column, row));
+
+            match first {
+                Some(player) if second == first && third == first => Some((player, line)),
+                _ => None,
+            }
+        })
+    }
+
+    // Returns player who has won, if any
+    pub fn winner(&self) -> Option<Player> {
+        self.winning_line().map(|(player, _)| player)
+    }
+
+    // Checks if every cell is taken
+    pub fn is_full(&self) -> bool {
+        self.cells.iter().flatten().all(Option::is_some)
+    }
+}
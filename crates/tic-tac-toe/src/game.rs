@@ -0,0 +1,183 @@
+use crate::{Board, Player};
+use pixelate::prelude::*;
+
+// Logical size of whole board in pixels
+pub const BOARD_SIZE: u32 = 240;
+// Logical size of a single cell in pixels
+pub const CELL_SIZE: u32 = BOARD_SIZE / 3;
+
+const BACKGROUND_COLOR: [u8; 4] = [24, 28, 36, 255];
+const GRID_COLOR: [u8; 4] = [92, 100, 116, 255];
+const X_COLOR: [u8; 4] = [232, 88, 72, 255];
+const O_COLOR: [u8; 4] = [72, 168, 232, 255];
+const WIN_COLOR: [u8; 4] = [248, 216, 96, 255];
+
+pub struct TicTacToe {
+    board: Board,
+    turn: Player,
+    cursor: (f32, f32),
+}
+
+impl TicTacToe {
+    // Creates a game on an empty board with X to move
+    pub fn new() -> Self {
+        Self {
+            board: Board::new(),
+            turn: Player::X,
+            cursor: (0.0, 0.0),
+        }
+    }
+
+    // Returns current board
+    pub fn board(&self) -> &Board {
+        &self.board
+    }
+
+    // Returns player whose move it is
+    pub fn turn(&self) -> Player {
+        self.turn
+    }
+
+    // Returns player who has won, if any
+    pub fn winner(&self) -> Option<Player> {
+        self.board.winner()
+    }
+
+    // Checks if game ended in a win or a draw
+    pub fn is_over(&self) -> bool {
+        self.board.winner().is_some() || self.board.is_full()
+    }
+
+    // Marks cell at given column and row for player to move, returning false if move is not allowed
+    pub fn play(&mut self, column: usize, row: usize) -> bool {
+        if self.is_over() || !self.board.place(column, row, self.turn) {
+            return false;
+        }
+
+        self.turn = self.turn.opponent();
+        true
+    }
+
+    // Clears board for a new game with X to move
+    pub fn restart(&mut self) {
+        *self = Self::new();
+    }
+
+    // Converts a position in logical pixels to column and row of cell under it
+    fn cell_at(x: f32, y: f32) -> Option<(usize, usize)> {
+        let size = BOARD_SIZE as f32;
+
+        if !(0.0..size).contains(&x) || !(0.0..size).contains(&y) {
+            return None;
+        }
+
+        Some((
+            x as usize / CELL_SIZE as usize,
+            y as usize / CELL_SIZE as usize,
+        ))
+    }
+
+    // Updates window title to show whose turn it is or how game ended
+    fn update_title(&self, window: &Window) {
+        let status = match (self.winner(), self.is_over()) {
+            (Some(player), _) => format!("{:?} wins", player),
+            (None, true) => "Draw".to_string(),
+            (None, false) => format!("{:?} to move", self.turn),
+        };
+
+        window.set_title(&format!("Tic Tac Toe - {}", status));
+    }
+
+    // Draws a mark centered in given cell
+    fn draw_mark(renderer: &mut Renderer, column: usize, row: usize, player: Player) {
+        let center_x = (column as u32 * CELL_SIZE + CELL_SIZE / 2) as i32;
+        let center_y = (row as u32 * CELL_SIZE + CELL_SIZE / 2) as i32;
+        let reach = (CELL_SIZE / 2 - 14) as i32;
+
+        match player {
+            Player::X => {
+                let style = LineStyle::new().thickness(6.0).cap(LineCap::Round);
+
+                for direction in [-1, 1] {
+                    renderer.draw_line_styled(
+                        (center_x - reach) as f32,
+                        (center_y - reach * direction) as f32,
+                        (center_x + reach) as f32,
+                        (center_y + reach * direction) as f32,
+                        &style,
+                        X_COLOR,
+                    );
+                }
+            }
+            Player::O => {
+                renderer.fill_circle(center_x, center_y, reach as u32, O_COLOR);
+                renderer.fill_circle(center_x, center_y, reach as u32 - 6, BACKGROUND_COLOR);
+            }
+        }
+    }
+}
+
+impl Default for TicTacToe {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Application for TicTacToe {
+    fn on_init(&mut self, window: &Window) -> Result<(), Box<dyn std::error::Error>> {
+        self.update_title(window);
+
+        Ok(())
+    }
+
+    fn on_event(&mut self, window: &Window, event: Event) {
+        match event {
+            Event::MouseMoved { x, y } => self.cursor = (x, y),
+            Event::MousePressed(MouseButton::Left) if self.is_over() => self.restart(),
+            Event::MousePressed(MouseButton::Left) => {
+                if let Some((column, row)) = Self::cell_at(self.cursor.0, self.cursor.1) {
+                    self.play(column, row);
+                }
+            }
+            Event::KeyPressed(KeyCode::R) => self.restart(),
+            _ => return,
+        }
+
+        self.update_title(window);
+    }
+
+    fn on_render(&mut self, _window: &Window, renderer: &mut Renderer) {
+        renderer.clear(BACKGROUND_COLOR);
+
+        for line in 1..3 {
+            let offset = (line * CELL_SIZE) as i32 - 2;
+
+            renderer.draw_rectangle(offset, 8, 4, BOARD_SIZE - 16, GRID_COLOR);
+            renderer.draw_rectangle(8, offset, BOARD_SIZE - 16, 4, GRID_COLOR);
+        }
+
+        for row in 0..3 {
+            for column in 0..3 {
+                if let Some(player) = self.board.cell(column, row) {
+                    Self::draw_mark(renderer, column, row, player);
+                }
+            }
+        }
+
+        if let Some((_, [(first_column, first_row), _, (last_column, last_row)])) =
+            self.board.winning_line()
+        {
+            let center = |cell: usize| (cell as u32 * CELL_SIZE + CELL_SIZE / 2) as f32;
+            let style = LineStyle::new().thickness(4.0).cap(LineCap::Round);
+
+            renderer.draw_line_styled(
+                center(first_column),
+                center(first_row),
+                center(last_column),
+                center(last_row),
+                &style,
+                WIN_COLOR,
+            );
+        }
+    }
+}
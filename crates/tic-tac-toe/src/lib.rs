@@ -0,0 +1,5 @@
+mod board;
+mod game;
+
+pub use board::{Board, Player};
+pub use game::{TicTacToe, BOARD_SIZE, CELL_SIZE};
@@ -1,27 +1,14 @@
 use pixelate::prelude::*;
-
-struct TicTacToe {
-    assets: AssetManager,
-}
-
-impl TicTacToe {
-    fn new() -> Self {
-        Self {
-            assets: AssetManager::new(),
-        }
-    }
-}
-
-impl Application for TicTacToe {
-    fn on_render(&mut self, _window: &Window, renderer: &mut Renderer) {
-        renderer.draw_rectangle(0, 0, 200, 200, [0, 225, 0, 225]);
-    }
-}
+use tic_tac_toe::{TicTacToe, BOARD_SIZE};
 
 fn main() {
     let application = TicTacToe::new();
     let window_config = WindowConfiguration {
         title: "Tic Tac Toe".to_string(),
+        width: BOARD_SIZE * 3,
+        height: BOARD_SIZE * 3,
+        logical_size: Some((BOARD_SIZE, BOARD_SIZE)),
+        redraw_mode: RedrawMode::OnDemand,
         ..Default::default()
     };
 
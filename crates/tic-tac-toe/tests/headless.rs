@@ -0,0 +1,72 @@
+use pixelate::prelude::*;
+use tic_tac_toe::{Player, TicTacToe, CELL_SIZE};
+
+fn window_config() -> WindowConfiguration {
+    WindowConfiguration {
+        width: CELL_SIZE * 3,
+        height: CELL_SIZE * 3,
+        ..Default::default()
+    }
+}
+
+// Schedules left clicks at centers of given cells, one click every other frame
+fn click_cells(cells: &[(u32, u32)]) -> InputScript {
+    cells
+        .iter()
+        .enumerate()
+        .fold(InputScript::new(), |script, (index, &(column, row))| {
+            script.click(
+                index as u32 * 2,
+                ((column * CELL_SIZE) + CELL_SIZE / 2) as f32,
+                ((row * CELL_SIZE) + CELL_SIZE / 2) as f32,
+                MouseButton::Left,
+            )
+        })
+}
+
+fn run(cells: &[(u32, u32)]) -> TicTacToe {
+    let headless_config = HeadlessConfiguration {
+        frames: cells.len() as u32 * 2 + 1,
+        script: click_cells(cells),
+        ..Default::default()
+    };
+
+    Engine::run_headless(TicTacToe::new(), window_config(), headless_config)
+        .expect("headless run failed")
+        .app
+}
+
+#[test]
+fn x_wins_by_taking_top_row() {
+    // X takes the top row while O plays the middle row
+    let game = run(&[(0, 0), (0, 1), (1, 0), (1, 1), (2, 0)]);
+
+    assert_eq!(game.winner(), Some(Player::X));
+    assert!(game.is_over());
+}
+
+#[test]
+fn taken_cell_does_not_pass_turn() {
+    let game = run(&[(1, 1), (1, 1)]);
+
+    assert_eq!(game.board().cell(1, 1), Some(Player::X));
+    assert_eq!(game.turn(), Player::O);
+}
+
+#[test]
+fn full_board_without_line_is_a_draw() {
+    let game = run(&[
+        (0, 0),
+        (1, 0),
+        (2, 0),
+        (1, 1),
+        (0, 1),
+        (2, 1),
+        (1, 2),
+        (0, 2),
+        (2, 2),
+    ]);
+
+    assert_eq!(game.winner(), None);
+    assert!(game.is_over());
+}
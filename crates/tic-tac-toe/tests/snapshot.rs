@@ -0,0 +1,39 @@
+use pixelate::testing::{snapshot_application, SnapshotConfiguration};
+use tic_tac_toe::{TicTacToe, BOARD_SIZE};
+
+fn snapshot(game: &mut TicTacToe, name: &str) {
+    if let Err(error) = snapshot_application(
+        game,
+        BOARD_SIZE,
+        BOARD_SIZE,
+        name,
+        &SnapshotConfiguration::default(),
+    ) {
+        panic!("{}", error);
+    }
+}
+
+#[test]
+fn empty_board() {
+    snapshot(&mut TicTacToe::new(), "empty_board");
+}
+
+#[test]
+fn board_in_progress() {
+    let mut game = TicTacToe::new();
+    for (column, row) in [(1, 1), (0, 0), (2, 0)] {
+        game.play(column, row);
+    }
+
+    snapshot(&mut game, "board_in_progress");
+}
+
+#[test]
+fn x_wins_diagonal() {
+    let mut game = TicTacToe::new();
+    for (column, row) in [(0, 0), (1, 0), (1, 1), (2, 0), (2, 2)] {
+        game.play(column, row);
+    }
+
+    snapshot(&mut game, "x_wins_diagonal");
+}